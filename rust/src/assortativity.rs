@@ -0,0 +1,173 @@
+use super::Graph;
+use rand::Rng;
+
+impl Graph {
+    /// Coefficient d'assortativité de degré de Newman: la corrélation de Pearson entre les
+    /// degrés des deux extrémités de chaque arête. Positif si les sommets de fort degré sont
+    /// reliés entre eux, négatif s'ils sont reliés aux sommets de faible degré. Vaut NaN si
+    /// toutes les arêtes relient des degrés identiques. Les boucles et les arêtes multiples sont
+    /// ignorées. Complexité: O(S+A).
+    /// ```
+    /// assert_eq!(-1.0, graph::Graph::star(5).assortativity());
+    /// ```
+    pub fn assortativity(&self) -> f64 {
+        let adjacency = self.simple_adjacency();
+        let (mut product, mut sum, mut square, mut count) = (0.0, 0.0, 0.0, 0.0);
+        for c in adjacency.iter() {
+            let du = c.len() as f64;
+            for &v in c.iter() {
+                let dv = adjacency[v].len() as f64;
+                product += du * dv;
+                sum += du;
+                square += du * du;
+                count += 1.0;
+            }
+        }
+        let mean = sum / count;
+        (product / count - mean * mean) / (square / count - mean * mean)
+    }
+    /// Degré moyen des voisins de chaque sommet, nul pour un sommet isolé. Les boucles et les
+    /// arêtes multiples sont ignorées. Complexité: O(S+A).
+    pub fn neighbour_degree(&self) -> Vec<f64> {
+        let adjacency = self.simple_adjacency();
+        adjacency
+            .iter()
+            .map(|c| {
+                if c.is_empty() {
+                    return 0.0;
+                }
+                c.iter().map(|&v| adjacency[v].len()).sum::<usize>() as f64 / c.len() as f64
+            })
+            .collect()
+    }
+    /// Degré moyen des voisins k_nn(k) en fonction du degré k: la moyenne de
+    /// `neighbour_degree` sur les sommets de degré k, `None` si aucun sommet n'a ce degré ou
+    /// pour k = 0. Longueur = degré maximal+1
+    pub fn knn(&self) -> Vec<Option<f64>> {
+        let adjacency = self.simple_adjacency();
+        let max = adjacency.iter().map(|c| c.len()).max().unwrap_or(0);
+        let mut sum = vec![0.0; max + 1];
+        let mut count = vec![0usize; max + 1];
+        for (c, knn) in adjacency.iter().zip(self.neighbour_degree()) {
+            sum[c.len()] += knn;
+            count[c.len()] += 1;
+        }
+        sum.iter()
+            .zip(count.iter())
+            .enumerate()
+            .map(|(k, (&s, &n))| {
+                if k == 0 || n == 0 {
+                    None
+                } else {
+                    Some(s / n as f64)
+                }
+            })
+            .collect()
+    }
+    /// Coefficient de club des riches φ(k) pour chaque seuil k: la densité du sous-graphe induit
+    /// par les sommets de degré strictement supérieur à k, `None` s'il en reste moins de deux.
+    /// Les boucles et les arêtes multiples sont ignorées. Longueur = degré maximal
+    /// ```
+    /// let phi = graph::Graph::complete(4).rich_club();
+    /// assert_eq!(vec![Some(1.0), Some(1.0), Some(1.0)], phi);
+    /// ```
+    pub fn rich_club(&self) -> Vec<Option<f64>> {
+        let adjacency = self.simple_adjacency();
+        let max = adjacency.iter().map(|c| c.len()).max().unwrap_or(0);
+        // Nombre de sommets et d'arêtes dont le plus petit degré vaut exactement k.
+        let mut nodes = vec![0usize; max + 1];
+        let mut edges = vec![0usize; max + 1];
+        for (u, c) in adjacency.iter().enumerate() {
+            nodes[c.len()] += 1;
+            for &v in c.iter().filter(|&&v| v > u) {
+                edges[c.len().min(adjacency[v].len())] += 1;
+            }
+        }
+        let (mut n, mut e) = (0, 0);
+        let mut phi = vec![None; max];
+        for k in (0..max).rev() {
+            n += nodes[k + 1];
+            e += edges[k + 1];
+            if n >= 2 {
+                phi[k] = Some(2.0 * e as f64 / (n * (n - 1)) as f64);
+            }
+        }
+        phi
+    }
+    /// Coefficient de club des riches normalisé ρ(k) = φ(k) / φ_alea(k), où φ_alea est la
+    /// moyenne sur `samples` graphes de même suite de degrés, obtenus par `rewire` avec 10 échanges
+    /// par arête. Une valeur supérieure à 1 indique que les sommets de fort
+    /// degré sont plus reliés entre eux que le hasard ne l'explique. `None` si φ(k) n'est pas
+    /// défini ou si φ_alea(k) est nul.
+    pub fn rich_club_normalized<R: Rng + ?Sized>(
+        &self,
+        samples: usize,
+        r: &mut R,
+    ) -> Vec<Option<f64>> {
+        let phi = self.rich_club();
+        let swaps = 10
+            * self
+                .simple_adjacency()
+                .iter()
+                .map(|c| c.len())
+                .sum::<usize>()
+            / 2;
+        let mut random = vec![0.0; phi.len()];
+        for _ in 0..samples {
+            let g = self.rewire(swaps, false, r);
+            for (sum, p) in random.iter_mut().zip(g.rich_club()) {
+                *sum += p.unwrap_or(0.0);
+            }
+        }
+        phi.iter()
+            .zip(random.iter())
+            .map(|(&p, &sum)| match p {
+                Some(p) if sum > 0.0 => Some(p * samples as f64 / sum),
+                _ => None,
+            })
+            .collect()
+    }
+}
+#[test]
+fn test_assortativity() {
+    let g = Graph::gen_barabasi_albert(300, &mut super::rng(3));
+    assert!(g.assortativity() < 0.0);
+    // Chaque arête relie un sommet de degré 3 à un sommet de degré 2.
+    assert_eq!(-1.0, Graph::complete_bipartite(2, 3).assortativity());
+    assert!(Graph::cycle(5).assortativity().is_nan());
+
+    let knn = Graph::star(4).knn();
+    assert_eq!(vec![None, Some(3.0), None, Some(1.0)], knn);
+    let g = Graph::gen_planted_partition(1, 60, 0.5, 0.5, &mut super::rng(4)).0;
+    let adjacency = g.simple_adjacency();
+    for (u, d) in g.neighbour_degree().iter().enumerate() {
+        let c = &adjacency[u];
+        let sum: usize = c.iter().map(|&v| adjacency[v].len()).sum();
+        assert!((d * c.len() as f64 - sum as f64).abs() < 1e-9);
+    }
+}
+#[test]
+fn test_rich_club() {
+    // Comparaison avec la définition.
+    let g = Graph::gen_barabasi_albert(200, &mut super::rng(5));
+    let adjacency = g.simple_adjacency();
+    for (k, phi) in g.rich_club().iter().enumerate() {
+        let rich: Vec<usize> = (0..g.len()).filter(|&n| adjacency[n].len() > k).collect();
+        let n = rich.len();
+        let e = rich
+            .iter()
+            .map(|&u| rich.iter().filter(|&v| adjacency[u].contains(v)).count())
+            .sum::<usize>()
+            / 2;
+        let expected = if n < 2 {
+            None
+        } else {
+            Some(2.0 * e as f64 / (n * (n - 1)) as f64)
+        };
+        assert_eq!(expected, *phi);
+    }
+
+    // Un graphe complet ne peut pas être modifié.
+    let rho = Graph::complete(6).rich_club_normalized(3, &mut super::rng(2));
+    assert!(rho.iter().all(|&x| x == Some(1.0)));
+}
@@ -0,0 +1,243 @@
+use super::gen::below;
+use super::Graph;
+use rand::Rng;
+use std::collections::VecDeque;
+
+/// Centralité d'intermédiarité, calculée par `Graph::betweenness`.
+#[derive(Debug, Clone)]
+pub struct Betweenness {
+    /// Score de chaque sommet: somme sur les paires de sommets de la proportion des plus courts
+    /// chemins qui passent par lui.
+    pub nodes: Vec<f64>,
+    /// Score de chaque arête `(a, b)` avec `a < b`, triées. Les boucles et les arêtes multiples
+    /// sont ignorées.
+    pub edges: Vec<((usize, usize), f64)>,
+}
+
+/// Indices des `k` plus grands scores, du plus grand au plus petit. En cas d'égalité le plus
+/// petit indice est en premier. Complexité: O(S + k log k).
+/// ```
+/// assert_eq!(vec![2, 0], graph::top_k(&[3.0, 1.0, 5.0, 3.0], 2));
+/// ```
+pub fn top_k(scores: &[f64], k: usize) -> Vec<usize> {
+    let cmp = |a: &usize, b: &usize| scores[*b].total_cmp(&scores[*a]).then(a.cmp(b));
+    let mut nodes: Vec<usize> = (0..scores.len()).collect();
+    let k = k.min(nodes.len());
+    if k == 0 {
+        return Vec::new();
+    }
+    nodes.select_nth_unstable_by(k - 1, cmp);
+    nodes.truncate(k);
+    nodes.sort_unstable_by(cmp);
+    nodes
+}
+
+impl Graph {
+    /// Intermédiarité exacte des sommets et des arêtes avec l'algorithme de Brandes: un parcours
+    /// en largeur depuis chaque sommet compte les plus courts chemins, puis les dépendances sont
+    /// accumulées dans l'ordre inverse du parcours. Chaque paire non ordonnée est comptée une
+    /// fois. Si `normalized`, les scores sont divisés par le nombre de paires possibles.
+    /// Complexité: O(S*A).
+    /// ```
+    /// let b = graph::Graph::path(3).betweenness(false);
+    /// assert_eq!(vec![0.0, 1.0, 0.0], b.nodes);
+    /// ```
+    pub fn betweenness(&self, normalized: bool) -> Betweenness {
+        let sources: Vec<usize> = (0..self.len()).collect();
+        self.brandes(&sources, normalized)
+    }
+    /// Estime l'intermédiarité en ne partant que de `pivots` sommets tirés sans remise, les
+    /// scores sont extrapolés à tous les sommets. Complexité: O(pivots*A).
+    pub fn betweenness_sampled<R: Rng + ?Sized>(
+        &self,
+        pivots: usize,
+        normalized: bool,
+        r: &mut R,
+    ) -> Betweenness {
+        let mut sources: Vec<usize> = (0..self.len()).collect();
+        let pivots = pivots.min(self.len());
+        for i in 0..pivots {
+            let j = i + below(r, self.len() - i);
+            sources.swap(i, j);
+        }
+        sources.truncate(pivots);
+        self.brandes(&sources, normalized)
+    }
+    /// Algorithme de Brandes depuis les sommets `sources`.
+    fn brandes(&self, sources: &[usize], normalized: bool) -> Betweenness {
+        let n = self.len();
+        let adjacency = self.simple_adjacency();
+        // Les arêtes (a, b) avec a < b sont numérotées par a puis b.
+        let mut offset = Vec::with_capacity(n + 1);
+        offset.push(0);
+        for (a, c) in adjacency.iter().enumerate() {
+            let upper = c.len() - c.partition_point(|&b| b <= a);
+            offset.push(offset[a] + upper);
+        }
+        let edge_index = |a: usize, b: usize| {
+            let (a, b) = (a.min(b), a.max(b));
+            let c = &adjacency[a];
+            let position = c.binary_search(&b).expect("the edge exists");
+            offset[a] + position - c.partition_point(|&x| x <= a)
+        };
+
+        let mut nodes = vec![0.0; n];
+        let mut edges = vec![0.0; offset[n]];
+        let mut order = Vec::with_capacity(n);
+        let mut queue = VecDeque::new();
+        let mut dist: Vec<Option<usize>> = vec![None; n];
+        let mut sigma = vec![0.0; n];
+        let mut delta = vec![0.0; n];
+        for &s in sources {
+            // Parcours en largeur sur la liste simple, où chaque arête existe dans les deux sens.
+            order.iter().for_each(|&v| dist[v] = None);
+            order.clear();
+            dist[s] = Some(0);
+            queue.push_back(s);
+            while let Some(u) = queue.pop_front() {
+                order.push(u);
+                let d = dist[u].map(|x| x + 1);
+                for &v in adjacency[u].iter() {
+                    if dist[v].is_none() {
+                        dist[v] = d;
+                        queue.push_back(v);
+                    }
+                }
+            }
+
+            // Nombre de plus courts chemins depuis s, les prédécesseurs sont à distance d-1.
+            for &v in order.iter() {
+                sigma[v] = if v == s { 1.0 } else { 0.0 };
+                delta[v] = 0.0;
+            }
+            for &v in order.iter().skip(1) {
+                let d = dist[v];
+                sigma[v] = adjacency[v]
+                    .iter()
+                    .filter(|&&u| dist[u].map(|x| x + 1) == d)
+                    .map(|&u| sigma[u])
+                    .sum();
+            }
+            for &v in order.iter().rev() {
+                let d = dist[v];
+                for &u in adjacency[v].iter() {
+                    if dist[u].map(|x| x + 1) == d {
+                        let c = sigma[u] / sigma[v] * (1.0 + delta[v]);
+                        delta[u] += c;
+                        edges[edge_index(u, v)] += c;
+                    }
+                }
+                if v != s {
+                    nodes[v] += delta[v];
+                }
+            }
+        }
+
+        // Chaque paire est vue depuis ses deux extrémités, et les sources tirées représentent
+        // tous les sommets.
+        let scale = 0.5 * n as f64 / sources.len().max(1) as f64;
+        let (mut node_scale, mut edge_scale) = (scale, scale);
+        if normalized {
+            let n = n as f64;
+            if n > 2.0 {
+                node_scale /= (n - 1.0) * (n - 2.0) / 2.0;
+            }
+            if n > 1.0 {
+                edge_scale /= n * (n - 1.0) / 2.0;
+            }
+        }
+        nodes.iter_mut().for_each(|x| *x *= node_scale);
+        let edges = adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(a, c)| c.iter().filter(move |&&b| a < b).map(move |&b| (a, b)))
+            .zip(edges)
+            .map(|(e, x)| (e, x * edge_scale))
+            .collect();
+        Betweenness { nodes, edges }
+    }
+    /// Centralité de proximité de chaque sommet: l'inverse de la distance moyenne vers les
+    /// sommets atteignables, multipliée par la proportion de sommets atteignables (correction
+    /// de Wasserman et Faust pour les graphes non connexes). Complexité: O(S*A).
+    pub fn closeness(&self) -> Vec<f64> {
+        let n = self.len();
+        self.all_distances(|distances| {
+            let reachable = distances.len() as f64;
+            let total: usize = distances.iter().sum();
+            if total == 0 {
+                0.0
+            } else {
+                reachable / total as f64 * reachable / (n - 1) as f64
+            }
+        })
+    }
+    /// Centralité harmonique de chaque sommet: la somme des inverses des distances vers les
+    /// autres sommets, un sommet inatteignable compte pour 0. Complexité: O(S*A).
+    pub fn harmonic(&self) -> Vec<f64> {
+        self.all_distances(|distances| distances.iter().map(|&d| 1.0 / d as f64).sum())
+    }
+    /// Applique `f` aux distances de chaque sommet vers les autres sommets atteignables.
+    fn all_distances<F: Fn(&[usize]) -> f64>(&self, f: F) -> Vec<f64> {
+        let whitelist = vec![true; self.len()];
+        let mut distances = Vec::new();
+        (0..self.len())
+            .map(|s| {
+                distances.clear();
+                self.bfs(s, &whitelist, &mut |_, d| {
+                    if d > 0 {
+                        distances.push(d)
+                    }
+                });
+                f(&distances)
+            })
+            .collect()
+    }
+}
+#[test]
+fn test_betweenness() {
+    // Étoile: le centre est sur le chemin de toutes les paires de feuilles.
+    let b = Graph::star(6).betweenness(false);
+    assert_eq!(10.0, b.nodes[0]);
+    assert!(b.nodes[1..].iter().all(|&x| x == 0.0));
+    assert!(b.edges.iter().all(|&(_, x)| x == 5.0));
+    assert_eq!(vec![0], top_k(&b.nodes, 1));
+    assert_eq!(1.0, Graph::star(6).betweenness(true).nodes[0]);
+
+    // Cycle de 4: deux plus courts chemins entre sommets opposés.
+    let b = Graph::cycle(4).betweenness(false);
+    assert!(b.nodes.iter().all(|&x| x == 0.5));
+    assert_eq!(4, b.edges.len());
+    assert!(b.edges.iter().all(|&(_, x)| x == 2.0));
+
+    // Toutes les sources tirées: l'estimation est exacte.
+    let g = Graph::gen_barabasi_albert(200, &mut super::rng(4));
+    let exact = g.betweenness(true);
+    let sampled = g.betweenness_sampled(200, true, &mut super::rng(5));
+    for (a, b) in exact.nodes.iter().zip(sampled.nodes.iter()) {
+        assert!((a - b).abs() < 1e-9);
+    }
+    let sampled = g.betweenness_sampled(100, true, &mut super::rng(5));
+    assert_eq!(top_k(&exact.nodes, 1), top_k(&sampled.nodes, 1));
+
+    // Arcs dans un seul sens: même résultat que sur le graphe symétrisé.
+    let g = Graph::gen_gilbert(12, &mut super::rng(1));
+    let symmetric = Graph {
+        adjacency_list: g.simple_adjacency(),
+        seed: None,
+    };
+    let (b, expected) = (g.betweenness(true), symmetric.betweenness(true));
+    assert_eq!(expected.nodes, b.nodes);
+    assert_eq!(expected.edges, b.edges);
+}
+#[test]
+fn test_closeness() {
+    // Chemin 0-1-2 et sommet isolé 3.
+    let g = Graph::new_iter(vec![(0, 1), (1, 2)].into_iter(), Some(4));
+    let c = g.closeness();
+    assert_eq!(2.0 / 3.0 * 2.0 / 3.0, c[0]);
+    assert_eq!(2.0 / 3.0, c[1]);
+    assert_eq!(0.0, c[3]);
+    let h = g.harmonic();
+    assert_eq!(vec![1.5, 2.0, 1.5, 0.0], h);
+    assert_eq!(vec![1, 0, 2], top_k(&h, 3));
+}
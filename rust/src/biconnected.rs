@@ -0,0 +1,234 @@
+use super::Graph;
+
+/// Points faibles du graphe, calculés par `Graph::biconnected`. Chaque arête `(a, b)` est donnée
+/// avec `a < b`.
+#[derive(Debug, Clone)]
+pub struct Biconnected {
+    /// Points d'articulation, par ordre croissant: les sommets dont le retrait augmente le nombre
+    /// de composantes connexes.
+    pub articulation: Vec<usize>,
+    /// Ponts, par ordre croissant: les arêtes dont le retrait augmente le nombre de composantes
+    /// connexes. Une arête multiple n'est jamais un pont.
+    pub bridges: Vec<(usize, usize)>,
+    /// Composantes biconnexes (blocs), chacune donnée par ses arêtes triées sans doublon.
+    pub components: Vec<Vec<(usize, usize)>>,
+}
+
+impl Biconnected {
+    /// Arbre des blocs et des points d'articulation: les sommets `0..components.len()` sont les
+    /// blocs, suivis des points d'articulation dans l'ordre de `articulation`. Chaque point
+    /// d'articulation est relié aux blocs qui le contiennent. C'est une forêt avec un arbre par
+    /// composante connexe ayant au moins une arête.
+    pub fn block_cut_tree(&self) -> Graph {
+        let blocks = self.components.len();
+        let mut tree = Graph::new(Some(blocks + self.articulation.len()));
+        let mut nodes: Vec<usize> = Vec::new();
+        for (b, component) in self.components.iter().enumerate() {
+            nodes.clear();
+            for &(x, y) in component.iter() {
+                nodes.push(x);
+                nodes.push(y);
+            }
+            nodes.sort_unstable();
+            nodes.dedup();
+            for n in nodes.iter() {
+                if let Ok(i) = self.articulation.binary_search(n) {
+                    tree.add((b, blocks + i));
+                }
+            }
+        }
+        tree
+    }
+}
+
+/// Sommet en cours d'exploration du parcours en profondeur.
+struct Frame {
+    node: usize,
+    parent: usize,
+    /// Indice du prochain enfant à explorer.
+    next: usize,
+    /// Vrai quand l'arête vers le parent a déjà été écartée une fois.
+    parent_skipped: bool,
+}
+
+impl Graph {
+    /// Points d'articulation, ponts et composantes biconnexes avec l'algorithme de Hopcroft et
+    /// Tarjan. Le parcours en profondeur utilise une pile explicite, il n'y a donc pas de risque
+    /// de débordement de la pile d'appel sur les graphes très profonds. Les boucles sont
+    /// ignorées. Complexité: O(S+A).
+    /// ```
+    /// let b = graph::Graph::path(4).biconnected();
+    /// assert_eq!(vec![1, 2], b.articulation);
+    /// assert_eq!(vec![(0, 1), (1, 2), (2, 3)], b.bridges);
+    /// ```
+    pub fn biconnected(&self) -> Biconnected {
+        let unvisited = usize::MAX;
+        let mut disc = vec![unvisited; self.len()];
+        let mut low = vec![0; self.len()];
+        let mut time = 0;
+        let mut is_articulation = vec![false; self.len()];
+        let mut bridges = Vec::new();
+        let mut components = Vec::new();
+        let mut frames: Vec<Frame> = Vec::new();
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+
+        for root in 0..self.len() {
+            if disc[root] != unvisited {
+                continue;
+            }
+            disc[root] = time;
+            low[root] = time;
+            time += 1;
+            let mut root_children = 0;
+            frames.push(Frame {
+                node: root,
+                parent: unvisited,
+                next: 0,
+                parent_skipped: false,
+            });
+
+            while let Some(frame) = frames.last_mut() {
+                let v = frame.node;
+                if let Some(&w) = self.adjacency_list[v].get(frame.next) {
+                    frame.next += 1;
+                    if w == v {
+                        continue;
+                    }
+                    // Seule une copie de l'arête vers le parent est celle de l'arbre.
+                    if w == frame.parent && !frame.parent_skipped {
+                        frame.parent_skipped = true;
+                        continue;
+                    }
+                    if disc[w] == unvisited {
+                        edges.push((v, w));
+                        disc[w] = time;
+                        low[w] = time;
+                        time += 1;
+                        frames.push(Frame {
+                            node: w,
+                            parent: v,
+                            next: 0,
+                            parent_skipped: false,
+                        });
+                    } else if disc[w] < disc[v] {
+                        edges.push((v, w));
+                        low[v] = low[v].min(disc[w]);
+                    }
+                    continue;
+                }
+
+                frames.pop();
+                let u = match frames.last() {
+                    Some(f) => f.node,
+                    None => break,
+                };
+                low[u] = low[u].min(low[v]);
+                if u == root {
+                    root_children += 1;
+                }
+                if low[v] >= disc[u] {
+                    // u sépare le sous-arbre de v: ses arêtes forment un bloc.
+                    if u != root {
+                        is_articulation[u] = true;
+                    }
+                    if low[v] > disc[u] {
+                        bridges.push((u.min(v), u.max(v)));
+                    }
+                    let mut component = Vec::new();
+                    while let Some((a, b)) = edges.pop() {
+                        component.push((a.min(b), a.max(b)));
+                        if (a, b) == (u, v) {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    component.dedup();
+                    components.push(component);
+                }
+            }
+            if root_children >= 2 {
+                is_articulation[root] = true;
+            }
+        }
+
+        bridges.sort_unstable();
+        Biconnected {
+            articulation: (0..self.len()).filter(|&n| is_articulation[n]).collect(),
+            bridges,
+            components,
+        }
+    }
+}
+#[test]
+fn test_biconnected() {
+    // Deux triangles partageant le sommet 2, un pont 4-5, une arête double 5-6 avec une boucle
+    // et un sommet isolé.
+    let mut g = Graph::new(Some(8));
+    for &e in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2), (4, 5)].iter() {
+        g.add(e);
+    }
+    g.add((5, 6));
+    g.add((6, 5));
+    g.add((6, 6));
+
+    let b = g.biconnected();
+    assert_eq!(vec![2, 4, 5], b.articulation);
+    assert_eq!(vec![(4, 5)], b.bridges);
+    let mut components = b.components.clone();
+    components.sort();
+    assert_eq!(
+        vec![
+            vec![(0, 1), (0, 2), (1, 2)],
+            vec![(2, 3), (2, 4), (3, 4)],
+            vec![(4, 5)],
+            vec![(5, 6)],
+        ],
+        components
+    );
+
+    let tree = b.block_cut_tree();
+    assert_eq!(7, tree.len());
+    assert_eq!(6, tree.edges());
+    assert!(tree
+        .bfs(0, &[true; 7], &mut |_, _| {})
+        .iter()
+        .all(|d| d.is_some()));
+
+    // Pas de récursion: un très long chemin ne déborde pas.
+    let b = Graph::path(1_000_000).biconnected();
+    assert_eq!(999_998, b.articulation.len());
+    assert_eq!(999_999, b.bridges.len());
+}
+#[test]
+fn biconnected_brute_force() {
+    let g = Graph::gen_geometric(200, 0.08, false, &mut super::rng(5)).0;
+    let components = |whitelist: &[bool]| {
+        let mut seen = vec![false; g.len()];
+        let mut count = 0;
+        for n in (0..g.len()).filter(|&n| whitelist[n]) {
+            if !seen[n] {
+                count += 1;
+                g.bfs(n, whitelist, &mut |m, _| seen[m] = true);
+            }
+        }
+        count
+    };
+    let all = vec![true; g.len()];
+    let reference = components(&all);
+
+    let b = g.biconnected();
+    let mut whitelist = all.clone();
+    for n in 0..g.len() {
+        whitelist[n] = false;
+        // Retirer un sommet isolé enlève une composante sans en créer.
+        let isolated = g.adjacency_list[n].is_empty();
+        let cut = !isolated && components(&whitelist) > reference;
+        assert_eq!(cut, b.articulation.binary_search(&n).is_ok(), "{}", n);
+        whitelist[n] = true;
+    }
+    assert!(!b.articulation.is_empty());
+
+    // Chaque arête appartient à exactement un bloc.
+    let total: usize = b.components.iter().map(|c| c.len()).sum();
+    assert_eq!(g.edges(), total);
+}
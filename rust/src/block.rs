@@ -0,0 +1,400 @@
+use super::gen::{below, shuffle, skip, triangular};
+use super::Graph;
+use rand::Rng;
+
+/// Paramètres du banc d'essai de type LFR (Lancichinetti-Fortunato-Radicchi), voir
+/// `Graph::gen_lfr`.
+#[derive(Debug, Clone)]
+pub struct Lfr {
+    /// Nombre de sommets.
+    pub size: usize,
+    /// Degré moyen visé.
+    pub degree_average: f64,
+    /// Degré maximal.
+    pub degree_max: usize,
+    /// Exposant de la loi de puissance des degrés.
+    pub tau1: f64,
+    /// Exposant de la loi de puissance des tailles de communautés.
+    pub tau2: f64,
+    /// Paramètre de mélange: proportion des arêtes de chaque sommet hors de sa communauté.
+    pub mu: f64,
+    /// Taille minimale d'une communauté.
+    pub community_min: usize,
+    /// Taille maximale d'une communauté.
+    pub community_max: usize,
+}
+
+impl Default for Lfr {
+    fn default() -> Lfr {
+        Lfr {
+            size: 1000,
+            degree_average: 15.0,
+            degree_max: 50,
+            tau1: 2.0,
+            tau2: 1.0,
+            mu: 0.1,
+            community_min: 20,
+            community_max: 100,
+        }
+    }
+}
+
+impl Graph {
+    /// Génère un graphe avec le modèle stochastique par blocs: le bloc `a` contient `sizes[a]`
+    /// sommets consécutifs, et deux sommets des blocs `a` et `b` sont reliés avec la probabilité
+    /// `probs[a][b]`. Les paires absentes sont sautées suivant une loi géométrique, la complexité
+    /// est donc O(S+A+B²) avec B le nombre de blocs. Retourne le graphe et le bloc de chaque
+    /// sommet.
+    /// ```
+    /// let (g, membership) = graph::Graph::gen_stochastic_block(
+    ///     &[3, 2],
+    ///     &[vec![1.0, 0.0], vec![0.0, 1.0]],
+    ///     &mut graph::rng(0),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(4, g.edges());
+    /// assert_eq!(vec![0, 0, 0, 1, 1], membership);
+    /// ```
+    pub fn gen_stochastic_block<R: Rng + ?Sized>(
+        sizes: &[usize],
+        probs: &[Vec<f64>],
+        r: &mut R,
+    ) -> Result<(Graph, Vec<usize>), String> {
+        let membership: Vec<usize> = sizes
+            .iter()
+            .enumerate()
+            .flat_map(|(b, &size)| std::iter::repeat_n(b, size))
+            .collect();
+        let edges = Graph::stream_stochastic_block(sizes, probs, r)?;
+        Ok((Graph::new_iter(edges, Some(membership.len())), membership))
+    }
+    /// Arêtes du modèle stochastique par blocs, voir `gen_stochastic_block`. La mémoire utilisée
+    /// ne dépend que du nombre de blocs.
+    pub fn stream_stochastic_block<'a, R: Rng + ?Sized>(
+        sizes: &[usize],
+        probs: &[Vec<f64>],
+        r: &'a mut R,
+    ) -> Result<impl Iterator<Item = (usize, usize)> + 'a, String> {
+        let blocks = sizes.len();
+        if probs.len() != blocks || probs.iter().any(|row| row.len() != blocks) {
+            return Err(format!("The probability matrix must be {0}×{0}", blocks));
+        }
+        let asymmetric = (0..blocks)
+            .flat_map(|a| (0..a).map(move |b| (a, b)))
+            .find(|&(a, b)| probs[a][b] != probs[b][a]);
+        if let Some(pair) = asymmetric {
+            return Err(format!(
+                "The probability matrix is not symmetric at {:?}",
+                pair
+            ));
+        }
+
+        let sizes = sizes.to_vec();
+        let probs = probs.to_vec();
+        // Indice du premier sommet de chaque bloc.
+        let offset: Vec<usize> = sizes
+            .iter()
+            .scan(0, |sum, &size| {
+                *sum += size;
+                Some(*sum - size)
+            })
+            .collect();
+
+        // Paire de blocs courante et prochaine paire de sommets à tester dans ces blocs.
+        let (mut a, mut b, mut next): (usize, usize, usize) = (0, 0, 0);
+        Ok(std::iter::from_fn(move || loop {
+            if a >= blocks {
+                return None;
+            }
+            let pairs = if a == b {
+                sizes[a] * sizes[a].saturating_sub(1) / 2
+            } else {
+                sizes[a] * sizes[b]
+            };
+            let i = next.saturating_add(skip(r, probs[a][b]));
+            if i < pairs {
+                next = i + 1;
+                let (x, y) = if a == b {
+                    triangular(i)
+                } else {
+                    (i / sizes[b], i % sizes[b])
+                };
+                return Some((offset[a] + x, offset[b] + y));
+            }
+            next = 0;
+            b += 1;
+            if b == blocks {
+                a += 1;
+                b = a;
+            }
+        }))
+    }
+    /// Modèle de partition planté: `groups` groupes de `group_size` sommets, deux sommets du même
+    /// groupe sont reliés avec la probabilité `p_in`, sinon avec `p_out`. Retourne le graphe et le
+    /// groupe de chaque sommet.
+    pub fn gen_planted_partition<R: Rng + ?Sized>(
+        groups: usize,
+        group_size: usize,
+        p_in: f64,
+        p_out: f64,
+        r: &mut R,
+    ) -> (Graph, Vec<usize>) {
+        let membership = (0..groups * group_size).map(|n| n / group_size).collect();
+        let edges = Graph::stream_planted_partition(groups, group_size, p_in, p_out, r);
+        (
+            Graph::new_iter(edges, Some(groups * group_size)),
+            membership,
+        )
+    }
+    /// Arêtes du modèle de partition planté, voir `gen_planted_partition`.
+    pub fn stream_planted_partition<'a, R: Rng + ?Sized>(
+        groups: usize,
+        group_size: usize,
+        p_in: f64,
+        p_out: f64,
+        r: &'a mut R,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let probs: Vec<Vec<f64>> = (0..groups)
+            .map(|a| {
+                (0..groups)
+                    .map(|b| if a == b { p_in } else { p_out })
+                    .collect()
+            })
+            .collect();
+        Graph::stream_stochastic_block(&vec![group_size; groups], &probs, r)
+            .expect("the planted partition matrix is square and symmetric")
+    }
+    /// Génère un banc d'essai de type LFR: les degrés et les tailles des communautés suivent des
+    /// lois de puissance, et chaque sommet a une proportion `mu` de ses arêtes hors de sa
+    /// communauté. Les arêtes sont tirées avec le modèle de configuration, les degrés obtenus
+    /// peuvent donc être légèrement inférieurs à ceux tirés. Les sommets en trop de la dernière
+    /// communauté, si elle est plus petite que `community_min`, sont répartis dans les autres;
+    /// elle n'est gardée que si toutes les autres ont déjà la taille `community_max`. Échoue si
+    /// `size` est nul ou inférieur à `community_min`, ou si `mu` n'est pas entre 0 et 1.
+    /// Retourne le graphe et la communauté de chaque sommet. Il n'y a pas de version `stream_`:
+    /// les demi-arêtes sont appariées de nouveau tant qu'elles forment des arêtes multiples, ce
+    /// qui demande le graphe entier.
+    pub fn gen_lfr<R: Rng + ?Sized>(param: &Lfr, r: &mut R) -> Result<(Graph, Vec<usize>), String> {
+        let n = param.size;
+        let degree_max = param.degree_max.max(1) as f64;
+        if param.degree_average < 1.0 || param.degree_average > degree_max {
+            return Err("The average degree must be between 1 and degree_max".to_string());
+        }
+        if param.community_min == 0 || param.community_min > param.community_max {
+            return Err("Invalid community size bounds".to_string());
+        }
+        if n == 0 || n < param.community_min {
+            return Err("The size must be at least community_min".to_string());
+        }
+        if !(0.0..=1.0).contains(&param.mu) {
+            return Err("The mixing parameter mu must be between 0 and 1".to_string());
+        }
+
+        // Recherche par dichotomie du degré minimal donnant le degré moyen visé.
+        let (mut low, mut high) = (1.0, degree_max);
+        for _ in 0..64 {
+            let mid = (low + high) / 2.0;
+            // Les degrés tirés sont arrondis à l'entier inférieur.
+            if power_law_mean(mid, degree_max + 1.0, param.tau1) - 0.5 < param.degree_average {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        let degrees: Vec<usize> = (0..n)
+            .map(|_| power_law(r, low, degree_max + 1.0, param.tau1) as usize)
+            .collect();
+
+        // Tailles des communautés jusqu'à couvrir tous les sommets.
+        let mut sizes: Vec<usize> = Vec::new();
+        let mut total = 0;
+        while total < n {
+            let s = power_law(
+                r,
+                param.community_min as f64,
+                param.community_max as f64 + 1.0,
+                param.tau2,
+            ) as usize;
+            sizes.push(s);
+            total += s;
+        }
+        let last = sizes.len() - 1;
+        sizes[last] -= total - n;
+        if sizes[last] < param.community_min {
+            // Répartit la dernière communauté, trop petite, dans les plus petites des autres.
+            let mut rest = sizes.pop().unwrap_or(0);
+            while rest > 0 {
+                match (0..sizes.len())
+                    .filter(|&c| sizes[c] < param.community_max)
+                    .min_by_key(|&c| sizes[c])
+                {
+                    Some(c) => sizes[c] += 1,
+                    None => break,
+                }
+                rest -= 1;
+            }
+            if rest > 0 {
+                sizes.push(rest);
+            }
+        }
+
+        // Chaque sommet rejoint une communauté assez grande pour son degré interne.
+        let internal: Vec<usize> = degrees
+            .iter()
+            .map(|&k| ((1.0 - param.mu) * k as f64).round() as usize)
+            .collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        shuffle(r, &mut order);
+        order.sort_by(|&a, &b| internal[b].cmp(&internal[a]));
+        let mut membership = vec![0; n];
+        let mut free = sizes.clone();
+        for &v in order.iter() {
+            let fitting: Vec<usize> = (0..sizes.len())
+                .filter(|&c| free[c] > 0 && sizes[c] > internal[v])
+                .collect();
+            let c = match fitting.len() {
+                0 => (0..sizes.len())
+                    .filter(|&c| free[c] > 0)
+                    .max_by_key(|&c| sizes[c]),
+                l => Some(fitting[below(r, l)]),
+            }
+            .expect("the community sizes sum to the number of nodes");
+            membership[v] = c;
+            free[c] -= 1;
+        }
+
+        let mut g = Graph::new(Some(n));
+        for c in 0..sizes.len() {
+            let members: Vec<usize> = (0..n).filter(|&v| membership[v] == c).collect();
+            let inner: Vec<usize> = members
+                .iter()
+                .map(|&v| internal[v].min(members.len() - 1))
+                .collect();
+            g.pair_stubs(&members, &inner, |_, _| true, r);
+        }
+        let external: Vec<usize> = (0..n).map(|v| degrees[v] - internal[v]).collect();
+        let all: Vec<usize> = (0..n).collect();
+        g.pair_stubs(&all, &external, |a, b| membership[a] != membership[b], r);
+
+        Ok((g, membership))
+    }
+    /// Ajoute au plus `degrees[i]` arêtes au sommet `nodes[i]` en appariant au hasard leurs
+    /// demi-arêtes. Les boucles, les arêtes existantes et celles refusées par `allowed` sont
+    /// écartées, puis les demi-arêtes restantes sont de nouveau appariées quelques fois.
+    fn pair_stubs<R, F>(&mut self, nodes: &[usize], degrees: &[usize], allowed: F, r: &mut R)
+    where
+        R: Rng + ?Sized,
+        F: Fn(usize, usize) -> bool,
+    {
+        let mut missing = degrees.to_vec();
+        for _ in 0..10 {
+            let mut stubs: Vec<usize> = Vec::new();
+            for (i, &d) in missing.iter().enumerate() {
+                stubs.extend(std::iter::repeat_n(i, d));
+            }
+            if stubs.len() < 2 {
+                return;
+            }
+            shuffle(r, &mut stubs);
+            for pair in stubs.chunks_exact(2) {
+                let (i, j) = (pair[0], pair[1]);
+                let (a, b) = (nodes[i], nodes[j]);
+                if a != b && allowed(a, b) && !self.adjacency_list[a].contains(&b) {
+                    self.add((a, b));
+                    missing[i] -= 1;
+                    missing[j] -= 1;
+                }
+            }
+        }
+    }
+}
+
+/// Tire un réel suivant une loi de puissance d'exposant `tau` sur `[min, max[`.
+fn power_law<R: Rng + ?Sized>(r: &mut R, min: f64, max: f64, tau: f64) -> f64 {
+    let x: f64 = r.gen();
+    let v = if (tau - 1.0).abs() < 1e-9 {
+        min * (max / min).powf(x)
+    } else {
+        let e = 1.0 - tau;
+        ((max.powf(e) - min.powf(e)) * x + min.powf(e)).powf(1.0 / e)
+    };
+    v.clamp(min, max.next_down())
+}
+
+/// Moyenne de la loi de puissance continue d'exposant `tau` sur `[min, max[`.
+fn power_law_mean(min: f64, max: f64, tau: f64) -> f64 {
+    let integral = |e: f64| {
+        if e.abs() < 1e-9 {
+            (max / min).ln()
+        } else {
+            (max.powf(e) - min.powf(e)) / e
+        }
+    };
+    integral(2.0 - tau) / integral(1.0 - tau)
+}
+#[test]
+fn gen_stochastic_block() {
+    use super::rng;
+    let probs = vec![
+        vec![1.0, 0.0, 0.0],
+        vec![0.0, 0.0, 1.0],
+        vec![0.0, 1.0, 0.0],
+    ];
+    let (g, membership) = Graph::gen_stochastic_block(&[4, 2, 3], &probs, &mut rng(0)).unwrap();
+    assert_eq!(6 + 6, g.edges());
+    assert_eq!(vec![0, 0, 0, 0, 1, 1, 2, 2, 2], membership);
+    assert_eq!(2, g.distance());
+    assert!(Graph::gen_stochastic_block(&[1, 2], &probs, &mut rng(0)).is_err());
+
+    let (g, membership) = Graph::gen_planted_partition(4, 50, 0.3, 0.01, &mut rng(1));
+    let inside = g
+        .edge_list()
+        .filter(|&(a, b)| a < b && membership[a] == membership[b])
+        .count();
+    assert!(inside > 4 * g.edges() / 5, "{} / {}", inside, g.edges());
+}
+#[test]
+fn gen_lfr() {
+    let param = Lfr::default();
+    let (g, membership) = Graph::gen_lfr(&param, &mut super::rng(5)).unwrap();
+    assert_eq!(1000, g.len());
+    assert_eq!(1000, membership.len());
+    let average = 2.0 * g.edges() as f64 / 1000.0;
+    assert!((average - 15.0).abs() < 2.0, "average degree: {}", average);
+    let outside = g
+        .edge_list()
+        .filter(|&(a, b)| a < b && membership[a] != membership[b])
+        .count();
+    let mu = outside as f64 / g.edges() as f64;
+    assert!(mu < 0.2, "mixing: {}", mu);
+    let mut sizes = vec![0; membership.iter().max().unwrap() + 1];
+    membership.iter().for_each(|&c| sizes[c] += 1);
+    assert!(
+        sizes.iter().all(|&s| (20..=100).contains(&s)),
+        "{:?}",
+        sizes
+    );
+
+    let invalid = [
+        Lfr {
+            size: 0,
+            ..Lfr::default()
+        },
+        Lfr {
+            mu: 1.5,
+            ..Lfr::default()
+        },
+        Lfr {
+            mu: -0.1,
+            ..Lfr::default()
+        },
+        Lfr {
+            community_min: 50,
+            community_max: 40,
+            ..Lfr::default()
+        },
+    ];
+    for param in invalid.iter() {
+        assert!(Graph::gen_lfr(param, &mut super::rng(5)).is_err());
+    }
+}
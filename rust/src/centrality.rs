@@ -0,0 +1,321 @@
+use super::Graph;
+
+/// Graphe vu par ses successeurs, pour les centralités calculées par itération de puissance.
+/// Le graphe non orienté `Graph` donne tous ses voisins; un graphe orienté donne les sommets
+/// pointés par chaque sommet. `Vec<Vec<usize>>` est une liste d'adjacence orientée.
+pub trait Successors {
+    /// Nombre de sommets.
+    fn nodes(&self) -> usize;
+    /// Successeurs du sommet `n`, avec répétition pour les arêtes multiples.
+    fn successors(&self, n: usize) -> &[usize];
+}
+
+impl Successors for Graph {
+    fn nodes(&self) -> usize {
+        self.len()
+    }
+    fn successors(&self, n: usize) -> &[usize] {
+        &self.adjacency_list[n]
+    }
+}
+
+impl Successors for Vec<Vec<usize>> {
+    fn nodes(&self) -> usize {
+        self.len()
+    }
+    fn successors(&self, n: usize) -> &[usize] {
+        &self[n]
+    }
+}
+
+/// Résultat d'une centralité calculée par itération de puissance.
+#[derive(Debug, Clone)]
+pub struct Centrality {
+    /// Score de chaque sommet.
+    pub scores: Vec<f64>,
+    /// Nombre d'itérations effectuées.
+    pub iterations: usize,
+    /// Somme des écarts absolus entre les scores des deux dernières itérations.
+    pub residual: f64,
+    /// Vrai si `residual` est passé sous la tolérance avant le nombre maximal d'itérations.
+    pub converged: bool,
+}
+
+/// Paramètres de `pagerank`.
+#[derive(Debug, Clone)]
+pub struct PageRank {
+    /// Probabilité de suivre une arête plutôt que de se téléporter.
+    pub damping: f64,
+    /// Probabilité de téléportation vers chaque sommet, uniforme si `None`. Normalisée si sa
+    /// somme diffère de 1.
+    pub personalization: Option<Vec<f64>>,
+    /// Répartition de la masse des sommets sans successeur, égale à la personnalisation si
+    /// `None`. Normalisée si sa somme diffère de 1.
+    pub dangling: Option<Vec<f64>>,
+    /// Arrêt quand `residual` est inférieur à cette tolérance.
+    pub tolerance: f64,
+    pub max_iterations: usize,
+}
+
+impl Default for PageRank {
+    fn default() -> PageRank {
+        PageRank {
+            damping: 0.85,
+            personalization: None,
+            dangling: None,
+            tolerance: 1e-10,
+            max_iterations: 1000,
+        }
+    }
+}
+
+/// Paramètres de `katz`.
+#[derive(Debug, Clone)]
+pub struct Katz {
+    /// Atténuation à chaque arête, doit être inférieure à l'inverse de la plus grande valeur
+    /// propre de la matrice d'adjacence pour converger.
+    pub alpha: f64,
+    /// Score reçu par chaque sommet indépendamment de ses voisins.
+    pub beta: f64,
+    /// Divise les scores par leur norme euclidienne.
+    pub normalized: bool,
+    /// Arrêt quand `residual` est inférieur à cette tolérance.
+    pub tolerance: f64,
+    pub max_iterations: usize,
+}
+
+impl Default for Katz {
+    fn default() -> Katz {
+        Katz {
+            alpha: 0.1,
+            beta: 1.0,
+            normalized: true,
+            tolerance: 1e-10,
+            max_iterations: 1000,
+        }
+    }
+}
+
+/// Vecteur de probabilité de taille `n`, uniforme si `v` est `None`.
+fn distribution(v: &Option<Vec<f64>>, n: usize, name: &str) -> Result<Vec<f64>, String> {
+    let v = match v {
+        None => return Ok(vec![1.0 / n as f64; n]),
+        Some(v) => v,
+    };
+    if v.len() != n {
+        return Err(format!(
+            "The {} vector has {} values for {} nodes",
+            name,
+            v.len(),
+            n
+        ));
+    }
+    let total: f64 = v.iter().sum();
+    if v.iter().any(|&x| x < 0.0) || total <= 0.0 {
+        return Err(format!("The {} vector is not a distribution", name));
+    }
+    Ok(v.iter().map(|x| x / total).collect())
+}
+
+/// Itère `step(précédent, suivant)` jusqu'à ce que l'écart passe sous `tolerance`.
+fn power_iteration<F>(
+    start: Vec<f64>,
+    tolerance: f64,
+    max_iterations: usize,
+    mut step: F,
+) -> Centrality
+where
+    F: FnMut(&[f64], &mut Vec<f64>),
+{
+    let mut scores = start;
+    let mut next = vec![0.0; scores.len()];
+    let mut residual = f64::INFINITY;
+    let mut iterations = 0;
+    while iterations < max_iterations && residual >= tolerance {
+        step(&scores, &mut next);
+        residual = scores
+            .iter()
+            .zip(next.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+        std::mem::swap(&mut scores, &mut next);
+        iterations += 1;
+    }
+    Centrality {
+        scores,
+        iterations,
+        residual,
+        converged: residual < tolerance,
+    }
+}
+
+/// Divise `v` par sa norme euclidienne.
+fn normalize(v: &mut [f64]) {
+    let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        v.iter_mut().for_each(|x| *x /= norm);
+    }
+}
+
+/// PageRank: probabilité stationnaire d'un marcheur qui suit une arête au hasard avec la
+/// probabilité `damping`, et se téléporte selon la personnalisation sinon. La masse des sommets
+/// sans successeur est répartie selon `dangling`. Les scores somment à 1. Complexité: O(A) par
+/// itération. Sur un graphe non orienté avec une personnalisation proportionnelle au degré, le
+/// score est lui aussi proportionnel au degré.
+/// ```
+/// let p = graph::pagerank(&graph::Graph::star(5), &graph::PageRank::default()).unwrap();
+/// assert!(p.converged);
+/// assert!(p.scores[0] > p.scores[1]);
+/// ```
+pub fn pagerank<G: Successors + ?Sized>(g: &G, options: &PageRank) -> Result<Centrality, String> {
+    let n = g.nodes();
+    if n == 0 {
+        return Ok(power_iteration(Vec::new(), options.tolerance, 0, |_, _| {}));
+    }
+    let personalization = distribution(&options.personalization, n, "personalization")?;
+    let dangling = match options.dangling {
+        None => personalization.clone(),
+        Some(_) => distribution(&options.dangling, n, "dangling")?,
+    };
+    let d = options.damping;
+
+    let start = vec![1.0 / n as f64; n];
+    Ok(power_iteration(
+        start,
+        options.tolerance,
+        options.max_iterations,
+        |scores, next| {
+            next.iter_mut().for_each(|x| *x = 0.0);
+            let mut lost = 0.0;
+            for (u, &x) in scores.iter().enumerate() {
+                let children = g.successors(u);
+                if children.is_empty() {
+                    lost += x;
+                    continue;
+                }
+                let share = d * x / children.len() as f64;
+                children.iter().for_each(|&v| next[v] += share);
+            }
+            for v in 0..n {
+                next[v] += d * lost * dangling[v] + (1.0 - d) * personalization[v];
+            }
+        },
+    ))
+}
+
+/// Centralité de vecteur propre: le vecteur propre principal de la transposée de la matrice
+/// d'adjacence, de norme euclidienne 1. Le score d'un sommet est proportionnel à la somme des
+/// scores de ses prédécesseurs. L'itération porte sur `I + A` pour converger aussi sur les
+/// graphes bipartis. Complexité: O(A) par itération.
+pub fn eigenvector<G: Successors + ?Sized>(
+    g: &G,
+    tolerance: f64,
+    max_iterations: usize,
+) -> Centrality {
+    let n = g.nodes();
+    let start = vec![1.0 / (n as f64).sqrt(); n];
+    power_iteration(start, tolerance, max_iterations, |scores, next| {
+        next.copy_from_slice(scores);
+        for (u, &x) in scores.iter().enumerate() {
+            g.successors(u).iter().for_each(|&v| next[v] += x);
+        }
+        normalize(next);
+    })
+}
+
+/// Centralité de Katz: solution de `x = alpha A^T x + beta`, chaque chemin de longueur `k`
+/// arrivant sur un sommet compte pour `alpha^k`. Complexité: O(A) par itération.
+pub fn katz<G: Successors + ?Sized>(g: &G, options: &Katz) -> Centrality {
+    let n = g.nodes();
+    let mut c = power_iteration(
+        vec![0.0; n],
+        options.tolerance,
+        options.max_iterations,
+        |scores, next| {
+            next.iter_mut().for_each(|x| *x = options.beta);
+            for (u, &x) in scores.iter().enumerate() {
+                g.successors(u)
+                    .iter()
+                    .for_each(|&v| next[v] += options.alpha * x);
+            }
+        },
+    );
+    if options.normalized {
+        normalize(&mut c.scores);
+    }
+    c
+}
+#[test]
+fn test_pagerank() {
+    // Non orienté, personnalisé par le degré: le PageRank est le degré divisé par 2A.
+    let g = Graph::gen_barabasi_albert(300, &mut super::rng(2));
+    let degree: Vec<f64> = g.adjacency_list.iter().map(|c| c.len() as f64).collect();
+    let options = PageRank {
+        personalization: Some(degree),
+        ..PageRank::default()
+    };
+    let p = pagerank(&g, &options).unwrap();
+    assert!(p.converged);
+    assert!(p.residual < 1e-10);
+    for n in 0..g.len() {
+        let expected = g.adjacency_list[n].len() as f64 / (2 * g.edges()) as f64;
+        assert!((p.scores[n] - expected).abs() < 1e-9);
+    }
+
+    // Orienté: 0 -> 1, 1 n'a pas de successeur et redistribue sa masse sur 0.
+    let directed = vec![vec![1], vec![]];
+    let options = PageRank {
+        dangling: Some(vec![1.0, 0.0]),
+        ..PageRank::default()
+    };
+    let p = pagerank(&directed, &options).unwrap();
+    assert!((p.scores[0] - p.scores[1]).abs() < 1e-9);
+
+    // Personnalisation sur le sommet 0 d'un chemin.
+    let options = PageRank {
+        personalization: Some(vec![1.0, 0.0, 0.0, 0.0]),
+        ..PageRank::default()
+    };
+    let p = pagerank(&Graph::path(4), &options).unwrap();
+    assert!((p.scores.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(p.scores[1] > p.scores[2] && p.scores[2] > p.scores[3]);
+    assert!(pagerank(&directed, &options).is_err());
+
+    let options = PageRank {
+        max_iterations: 3,
+        ..PageRank::default()
+    };
+    let p = pagerank(&g, &options).unwrap();
+    assert_eq!(3, p.iterations);
+    assert!(!p.converged);
+}
+#[test]
+fn test_eigenvector_katz() {
+    // Étoile: le centre vaut sqrt(feuilles) fois une feuille.
+    let c = eigenvector(&Graph::star(10), 1e-12, 1000);
+    assert!(c.converged);
+    assert!((c.scores[0] / c.scores[1] - 3.0).abs() < 1e-6);
+    let norm: f64 = c.scores.iter().map(|x| x * x).sum();
+    assert!((norm - 1.0).abs() < 1e-9);
+
+    // Cycle: chaque sommet vaut beta / (1 - 2 alpha).
+    let options = Katz {
+        normalized: false,
+        ..Katz::default()
+    };
+    let c = katz(&Graph::cycle(7), &options);
+    assert!(c.converged);
+    assert!(c.scores.iter().all(|x| (x - 1.0 / 0.8).abs() < 1e-9));
+
+    // Orienté: 0 -> 1 -> 2, les scores croissent le long du chemin.
+    let c = katz(&vec![vec![1], vec![2], vec![]], &options);
+    assert!((c.scores[2] - (1.0 + 0.1 + 0.01)).abs() < 1e-12);
+
+    // alpha trop grand: la série diverge.
+    let options = Katz {
+        alpha: 0.6,
+        max_iterations: 50,
+        ..options
+    };
+    assert!(!katz(&Graph::cycle(7), &options).converged);
+}
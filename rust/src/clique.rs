@@ -0,0 +1,211 @@
+use super::{intersection, Graph};
+
+/// Itérateur des cliques maximales, voir `Graph::cliques`.
+pub struct Cliques {
+    adjacency: Vec<Vec<usize>>,
+    order: Vec<usize>,
+    rank: Vec<usize>,
+    /// Position dans l'ordre de dégénérescence du prochain sommet de départ.
+    outer: usize,
+    /// Clique en construction.
+    clique: Vec<usize>,
+    stack: Vec<Frame>,
+}
+
+/// Étape de Bron-Kerbosch: les candidats `p` pour agrandir la clique, les sommets `x` déjà
+/// explorés, et les candidats restant à essayer (ceux non voisins du pivot).
+struct Frame {
+    p: Vec<usize>,
+    x: Vec<usize>,
+    branches: Vec<usize>,
+    next: usize,
+}
+
+impl Cliques {
+    /// Nouvelle étape, le pivot est le sommet de `p` ou `x` ayant le plus de voisins dans `p`:
+    /// seuls les candidats qui ne sont pas ses voisins sont essayés.
+    fn frame(&self, p: Vec<usize>, x: Vec<usize>) -> Frame {
+        let neighbours_in_p = |u: usize| {
+            let c = &self.adjacency[u];
+            p.iter().filter(|v| c.binary_search(v).is_ok()).count()
+        };
+        let pivot = p
+            .iter()
+            .chain(x.iter())
+            .copied()
+            .max_by_key(|&u| (neighbours_in_p(u), std::cmp::Reverse(u)));
+        let branches = match pivot {
+            Some(u) => p
+                .iter()
+                .copied()
+                .filter(|v| self.adjacency[u].binary_search(v).is_err())
+                .collect(),
+            None => Vec::new(),
+        };
+        Frame {
+            p,
+            x,
+            branches,
+            next: 0,
+        }
+    }
+    /// Clique courante, triée.
+    fn current(&self) -> Vec<usize> {
+        let mut c = self.clique.clone();
+        c.sort_unstable();
+        c
+    }
+}
+
+impl Iterator for Cliques {
+    type Item = Vec<usize>;
+    fn next(&mut self) -> Option<Vec<usize>> {
+        loop {
+            let frame = match self.stack.last_mut() {
+                Some(frame) => frame,
+                None => {
+                    // Nouveau départ: les voisins suivants dans l'ordre sont candidats, les
+                    // précédents ont déjà été explorés.
+                    let v = *self.order.get(self.outer)?;
+                    self.outer += 1;
+                    let (rank, c) = (&self.rank, &self.adjacency[v]);
+                    let (p, x): (Vec<usize>, Vec<usize>) =
+                        c.iter().partition(|&&u| rank[u] > rank[v]);
+                    if c.is_empty() {
+                        return Some(vec![v]);
+                    }
+                    self.clique.push(v);
+                    let frame = self.frame(p, x);
+                    self.stack.push(frame);
+                    continue;
+                }
+            };
+
+            let v = match frame.branches.get(frame.next) {
+                Some(&v) => v,
+                None => {
+                    self.stack.pop();
+                    self.clique.pop();
+                    continue;
+                }
+            };
+            frame.next += 1;
+            let c = &self.adjacency[v];
+            let p: Vec<usize> = intersection(&frame.p, c).collect();
+            let x: Vec<usize> = intersection(&frame.x, c).collect();
+            // v passe des candidats aux sommets explorés.
+            if let Ok(i) = frame.p.binary_search(&v) {
+                frame.p.remove(i);
+            }
+            if let Err(i) = frame.x.binary_search(&v) {
+                frame.x.insert(i, v);
+            }
+
+            if p.is_empty() {
+                if x.is_empty() {
+                    self.clique.push(v);
+                    let clique = self.current();
+                    self.clique.pop();
+                    return Some(clique);
+                }
+                continue;
+            }
+            self.clique.push(v);
+            let frame = self.frame(p, x);
+            self.stack.push(frame);
+        }
+    }
+}
+
+impl Graph {
+    /// Énumère les cliques maximales avec l'algorithme de Bron-Kerbosch avec pivot: chaque
+    /// sommet, dans l'ordre de dégénérescence, démarre une recherche limitée à ses voisins
+    /// suivants, ce qui borne le nombre de candidats par la dégénérescence. Chaque clique est
+    /// triée; un sommet isolé est une clique d'un sommet. Les boucles et les arêtes multiples sont
+    /// ignorées. L'itérateur calcule les cliques à la demande, il peut donc être interrompu.
+    /// ```
+    /// let mut g = graph::Graph::complete(3);
+    /// g.push((2, 3));
+    /// let cliques: Vec<Vec<usize>> = g.cliques().collect();
+    /// assert_eq!(2, cliques.len());
+    /// assert!(cliques.contains(&vec![0, 1, 2]));
+    /// ```
+    pub fn cliques(&self) -> Cliques {
+        let order = self.cores().order;
+        let mut rank = vec![0; self.len()];
+        order.iter().enumerate().for_each(|(i, &n)| rank[n] = i);
+        Cliques {
+            adjacency: self.simple_adjacency(),
+            order,
+            rank,
+            outer: 0,
+            clique: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+    /// Appelle `f` sur chaque clique maximale, voir `cliques`. L'énumération s'arrête dès que
+    /// `f` retourne faux.
+    pub fn for_each_clique<F: FnMut(&[usize]) -> bool>(&self, mut f: F) {
+        for clique in self.cliques() {
+            if !f(&clique) {
+                break;
+            }
+        }
+    }
+    /// Nombre de cliques maximales de chaque taille. Longueur = nombre de clique+1
+    pub fn clique_distrib(&self) -> Vec<usize> {
+        let mut distrib = Vec::new();
+        for clique in self.cliques() {
+            if distrib.len() <= clique.len() {
+                distrib.resize(clique.len() + 1, 0);
+            }
+            distrib[clique.len()] += 1;
+        }
+        distrib
+    }
+    /// Nombre de clique: la taille de la plus grande clique.
+    pub fn clique_number(&self) -> usize {
+        self.cliques().map(|c| c.len()).max().unwrap_or(0)
+    }
+}
+#[test]
+fn test_cliques() {
+    assert_eq!(
+        vec![vec![0, 1, 2, 3, 4]],
+        Graph::complete(5).cliques().collect::<Vec<_>>()
+    );
+    assert_eq!(vec![0, 0, 15], Graph::petersen().clique_distrib());
+    assert_eq!(4, Graph::wheel(4).clique_number());
+
+    // Comparaison avec tous les sous-ensembles de sommets.
+    let g = Graph::gen_planted_partition(1, 14, 0.5, 0.5, &mut super::rng(6)).0;
+    let adjacency = g.simple_adjacency();
+    let is_clique = |s: u32| {
+        (0..14).all(|a| {
+            (0..14).all(|b| {
+                a == b || s & (1 << a) == 0 || s & (1 << b) == 0 || adjacency[a].contains(&b)
+            })
+        })
+    };
+    let mut expected: Vec<Vec<usize>> = (1u32..1 << 14)
+        .filter(|&s| is_clique(s) && (0..14).all(|n| s & (1 << n) != 0 || !is_clique(s | 1 << n)))
+        .map(|s| (0..14).filter(|&n| s & (1 << n) != 0).collect())
+        .collect();
+    expected.sort();
+    let mut found: Vec<Vec<usize>> = g.cliques().collect();
+    found.sort();
+    assert_eq!(expected, found);
+
+    let mut seen = 0;
+    g.for_each_clique(|_| {
+        seen += 1;
+        seen < 3
+    });
+    assert_eq!(3, seen);
+
+    let s = g.stats_with(&super::StatsOptions {
+        cliques: true,
+        ..super::StatsOptions::default()
+    });
+    assert_eq!(Some(g.clique_distrib()), s.clique_distrib);
+}
@@ -0,0 +1,248 @@
+use super::Graph;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+
+/// Résultat de `Graph::bipartite`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Bipartite {
+    /// Le côté, 0 ou 1, de chaque sommet: aucune arête ne relie deux sommets du même côté.
+    Bipartition(Vec<usize>),
+    /// Un cycle de longueur impaire, qui prouve que le graphe n'est pas biparti: chaque sommet
+    /// est relié au suivant, et le dernier au premier. Une boucle donne un cycle d'un sommet.
+    OddCycle(Vec<usize>),
+}
+
+/// Graphe à une seule classe de sommets obtenu par `Graph::projection`.
+#[derive(Debug)]
+pub struct Projection {
+    /// Graphe simple des sommets projetés.
+    pub graph: Graph,
+    /// Indice d'origine de chaque sommet projeté.
+    pub nodes: Vec<usize>,
+    /// Poids de chaque arête `(a, b)` de `graph` avec `a < b`, triées: le nombre de voisins
+    /// communs dans le graphe d'origine.
+    pub weights: Vec<((usize, usize), usize)>,
+}
+
+/// Coloration des sommets, calculée par `Graph::greedy_colouring` ou `Graph::dsatur`.
+#[derive(Debug, Clone)]
+pub struct Colouring {
+    /// Couleur de chaque sommet, deux sommets voisins ont des couleurs différentes.
+    pub colours: Vec<usize>,
+    /// Nombre de couleurs utilisées.
+    pub count: usize,
+}
+
+impl Graph {
+    /// Teste si le graphe est biparti par un parcours en largeur de chaque composante connexe:
+    /// les sommets à distance paire de la racine forment un côté. Si une arête relie deux
+    /// sommets à la même distance, les chemins vers leur ancêtre commun forment un cycle impair.
+    /// Complexité: O(S+A).
+    /// ```
+    /// use graph::{Bipartite, Graph};
+    /// assert_eq!(Bipartite::Bipartition(vec![0, 1, 0, 1]), Graph::cycle(4).bipartite());
+    /// assert_eq!(Bipartite::OddCycle(vec![1, 0, 2]), Graph::cycle(3).bipartite());
+    /// ```
+    pub fn bipartite(&self) -> Bipartite {
+        let unseen = usize::MAX;
+        let mut dist = vec![unseen; self.len()];
+        let mut parent = vec![unseen; self.len()];
+        let mut queue = VecDeque::new();
+        for root in 0..self.len() {
+            if dist[root] != unseen {
+                continue;
+            }
+            dist[root] = 0;
+            queue.push_back(root);
+            while let Some(u) = queue.pop_front() {
+                for &v in self.adjacency_list[u].iter() {
+                    if dist[v] == unseen {
+                        dist[v] = dist[u] + 1;
+                        parent[v] = u;
+                        queue.push_back(v);
+                    } else if dist[v] == dist[u] {
+                        // Remonte les deux branches jusqu'à l'ancêtre commun.
+                        let (mut a, mut b) = (u, v);
+                        let mut left = vec![a];
+                        let mut right = vec![b];
+                        while a != b {
+                            a = parent[a];
+                            b = parent[b];
+                            left.push(a);
+                            right.push(b);
+                        }
+                        right.pop();
+                        left.extend(right.into_iter().rev());
+                        return Bipartite::OddCycle(left);
+                    }
+                }
+            }
+        }
+        Bipartite::Bipartition(dist.iter().map(|d| d % 2).collect())
+    }
+    /// Projection sur les sommets `n` tels que `keep[n]`: deux sommets gardés sont reliés s'ils
+    /// ont un voisin commun parmi les autres sommets, avec pour poids le nombre de ces voisins
+    /// (par exemple deux utilisateurs et les dépôts auxquels ils ont tous deux contribué). Les
+    /// arêtes entre sommets gardés, les boucles et les arêtes multiples sont ignorées.
+    /// Complexité: O(S + somme des carrés des degrés des autres sommets).
+    pub fn projection(&self, keep: &[bool]) -> Projection {
+        let adjacency = self.simple_adjacency();
+        let nodes: Vec<usize> = (0..self.len()).filter(|&n| keep[n]).collect();
+        let mut index = vec![usize::MAX; self.len()];
+        nodes.iter().enumerate().for_each(|(i, &n)| index[n] = i);
+
+        let mut graph = Graph::new(Some(nodes.len()));
+        let mut weights = Vec::new();
+        let mut count = vec![0; nodes.len()];
+        let mut touched: Vec<usize> = Vec::new();
+        for (a, &u) in nodes.iter().enumerate() {
+            for &m in adjacency[u].iter().filter(|&&m| !keep[m]) {
+                for &v in adjacency[m].iter().filter(|&&v| keep[v]) {
+                    let b = index[v];
+                    if b > a {
+                        if count[b] == 0 {
+                            touched.push(b);
+                        }
+                        count[b] += 1;
+                    }
+                }
+            }
+            touched.sort_unstable();
+            for &b in touched.iter() {
+                graph.add((a, b));
+                weights.push(((a, b), count[b]));
+                count[b] = 0;
+            }
+            touched.clear();
+        }
+
+        Projection {
+            graph,
+            nodes,
+            weights,
+        }
+    }
+    /// Coloration gloutonne: les sommets, du plus grand degré au plus petit, prennent la plus
+    /// petite couleur absente de leurs voisins (ordre de Welsh et Powell). Les boucles sont
+    /// ignorées. Complexité: O(S log S + A).
+    pub fn greedy_colouring(&self) -> Colouring {
+        let adjacency = self.simple_adjacency();
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_by_key(|&n| (Reverse(adjacency[n].len()), n));
+
+        let unset = usize::MAX;
+        let mut colours = vec![unset; self.len()];
+        let mut used: Vec<bool> = Vec::new();
+        for &u in order.iter() {
+            used.clear();
+            used.resize(adjacency[u].len() + 1, false);
+            for &v in adjacency[u].iter() {
+                if colours[v] < used.len() {
+                    used[colours[v]] = true;
+                }
+            }
+            colours[u] = used.iter().position(|&x| !x).unwrap_or(0);
+        }
+        colouring(colours)
+    }
+    /// Coloration DSatur de Brélaz: le prochain sommet coloré est celui dont les voisins ont
+    /// le plus de couleurs différentes (puis le plus de voisins), il prend la plus petite couleur
+    /// disponible. Exacte sur les graphes bipartis. Les boucles sont ignorées.
+    /// Complexité: O((S+A) log S).
+    /// ```
+    /// assert_eq!(3, graph::Graph::petersen().dsatur().count);
+    /// ```
+    pub fn dsatur(&self) -> Colouring {
+        let adjacency = self.simple_adjacency();
+        let unset = usize::MAX;
+        let mut colours = vec![unset; self.len()];
+        let mut saturation: Vec<HashSet<usize>> = vec![HashSet::new(); self.len()];
+        let mut heap: BinaryHeap<(usize, usize, Reverse<usize>)> = (0..self.len())
+            .map(|n| (0, adjacency[n].len(), Reverse(n)))
+            .collect();
+        while let Some((s, _, Reverse(u))) = heap.pop() {
+            if colours[u] != unset || s != saturation[u].len() {
+                continue;
+            }
+            let c = (0..).find(|c| !saturation[u].contains(c)).unwrap_or(0);
+            colours[u] = c;
+            for &v in adjacency[u].iter() {
+                if colours[v] == unset && saturation[v].insert(c) {
+                    heap.push((saturation[v].len(), adjacency[v].len(), Reverse(v)));
+                }
+            }
+        }
+        colouring(colours)
+    }
+}
+
+fn colouring(colours: Vec<usize>) -> Colouring {
+    let count = colours.iter().max().map_or(0, |c| c + 1);
+    Colouring { colours, count }
+}
+#[test]
+fn test_bipartite() {
+    let g = Graph::grid(&[5, 6]);
+    match g.bipartite() {
+        Bipartite::Bipartition(side) => {
+            assert!(g.edge_list().all(|(a, b)| side[a] != side[b]));
+        }
+        Bipartite::OddCycle(_) => panic!("the grid is bipartite"),
+    }
+
+    let mut g = Graph::petersen();
+    g.push((10, 11));
+    for g in [g, Graph::cycle(7), Graph::wheel(8)].iter() {
+        match g.bipartite() {
+            Bipartite::OddCycle(cycle) => {
+                assert_eq!(1, cycle.len() % 2);
+                for i in 0..cycle.len() {
+                    let next = cycle[(i + 1) % cycle.len()];
+                    assert!(g.adjacency_list[cycle[i]].contains(&next));
+                }
+            }
+            Bipartite::Bipartition(_) => panic!("odd cycle expected"),
+        }
+    }
+
+    let mut g = Graph::path(3);
+    g.add((2, 2));
+    assert_eq!(Bipartite::OddCycle(vec![2]), g.bipartite());
+}
+#[test]
+fn test_projection() {
+    // Trois utilisateurs (0..3) et quatre dépôts (3..7).
+    let mut g = Graph::new(Some(7));
+    for &e in [(0, 3), (0, 4), (1, 3), (1, 4), (1, 5), (2, 6), (0, 3)].iter() {
+        g.add(e);
+    }
+    let keep: Vec<bool> = (0..7).map(|n| n < 3).collect();
+    let p = g.projection(&keep);
+    assert_eq!(vec![0, 1, 2], p.nodes);
+    assert_eq!(vec![((0, 1), 2)], p.weights);
+    assert_eq!(1, p.graph.edges());
+
+    let p =
+        Graph::complete_bipartite(3, 4).projection(&[true, true, true, false, false, false, false]);
+    assert_eq!(3, p.graph.edges());
+    assert!(p.weights.iter().all(|&(_, w)| w == 4));
+}
+#[test]
+fn test_colouring() {
+    let cases = [
+        (Graph::grid(&[7, 8]), 2),
+        (Graph::petersen(), 3),
+        (Graph::complete(6), 6),
+        (Graph::wheel(6), 4),
+        (Graph::cycle(9), 3),
+    ];
+    for (g, chromatic) in cases.iter() {
+        let d = g.dsatur();
+        assert_eq!(*chromatic, d.count);
+        let greedy = g.greedy_colouring();
+        assert!(greedy.count >= *chromatic);
+        for c in [d, greedy].iter() {
+            assert!(g.edge_list().all(|(a, b)| c.colours[a] != c.colours[b]));
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use super::gen::{below, shuffle};
+use super::Graph;
+use rand::Rng;
+
+/// Hiérarchie de communautés trouvée par `Graph::louvain`. Une partition associe à chaque sommet
+/// le numéro de sa communauté, numérotées à partir de 0 dans l'ordre d'apparition.
+#[derive(Debug, Clone)]
+pub struct Louvain {
+    /// Partition des sommets du graphe après chaque niveau, de la plus fine à la plus grossière.
+    pub levels: Vec<Vec<usize>>,
+    /// Modularité de chaque niveau à la résolution utilisée, celle que la méthode optimise, voir
+    /// `Graph::modularity_with`.
+    pub modularity: Vec<f64>,
+}
+
+impl Louvain {
+    /// Partition du dernier niveau, la plus grossière.
+    pub fn partition(&self) -> &[usize] {
+        self.levels.last().map(|p| &p[..]).unwrap_or(&[])
+    }
+}
+
+/// Nombre maximal de passes sur tous les sommets de `Weighted::local_moves`.
+const MAX_PASSES: usize = 100;
+
+/// Graphe pondéré d'un niveau de Louvain. Une boucle de poids `w` est notée `(n, 2w)` dans la
+/// liste de `n`, ainsi le degré est la somme des poids de la liste.
+struct Weighted {
+    adjacency: Vec<Vec<(usize, f64)>>,
+}
+
+impl Weighted {
+    fn degree(&self, n: usize) -> f64 {
+        self.adjacency[n].iter().map(|&(_, w)| w).sum()
+    }
+    /// Déplace chaque sommet vers la communauté voisine qui augmente le plus la modularité,
+    /// jusqu'à ce qu'aucun déplacement ne l'améliore, en au plus `MAX_PASSES` passes. Retourne la communauté de chaque sommet et
+    /// si au moins un sommet a changé de communauté.
+    fn local_moves(&self, resolution: f64) -> (Vec<usize>, bool) {
+        let n = self.adjacency.len();
+        let degree: Vec<f64> = (0..n).map(|u| self.degree(u)).collect();
+        let total: f64 = degree.iter().sum();
+        let mut community: Vec<usize> = (0..n).collect();
+        let mut tot = degree.clone();
+        if total == 0.0 {
+            return (community, false);
+        }
+
+        // Poids des arêtes vers chaque communauté voisine, remis à zéro après chaque sommet.
+        let mut links = vec![0.0; n];
+        let mut neighbours: Vec<usize> = Vec::new();
+        let mut moved = false;
+        let mut improved = true;
+        let mut passes = 0;
+        while improved && passes < MAX_PASSES {
+            improved = false;
+            passes += 1;
+            for u in 0..n {
+                let old = community[u];
+                neighbours.clear();
+                neighbours.push(old);
+                for &(v, w) in self.adjacency[u].iter() {
+                    if v == u {
+                        continue;
+                    }
+                    let c = community[v];
+                    if links[c] == 0.0 {
+                        neighbours.push(c);
+                    }
+                    links[c] += w;
+                }
+
+                tot[old] -= degree[u];
+                let gain = |c: usize| links[c] - resolution * tot[c] * degree[u] / total;
+                let mut best = old;
+                let mut best_gain = gain(old);
+                for &c in neighbours.iter() {
+                    let g = gain(c);
+                    if g > best_gain + 1e-12 {
+                        best = c;
+                        best_gain = g;
+                    }
+                }
+                tot[best] += degree[u];
+                community[u] = best;
+                if best != old {
+                    improved = true;
+                    moved = true;
+                }
+                neighbours.iter().for_each(|&c| links[c] = 0.0);
+            }
+        }
+
+        (community, moved)
+    }
+    /// Graphe des communautés: un sommet par communauté, les poids des arêtes sont sommés.
+    fn aggregate(&self, community: &[usize], count: usize) -> Weighted {
+        let mut adjacency: Vec<Vec<(usize, f64)>> = vec![Vec::new(); count];
+        for (u, list) in self.adjacency.iter().enumerate() {
+            let cu = community[u];
+            adjacency[cu].extend(list.iter().map(|&(v, w)| (community[v], w)));
+        }
+        for list in adjacency.iter_mut() {
+            list.sort_unstable_by_key(|&(v, _)| v);
+            let mut merged: Vec<(usize, f64)> = Vec::with_capacity(list.len());
+            for &(v, w) in list.iter() {
+                match merged.last_mut() {
+                    Some(last) if last.0 == v => last.1 += w,
+                    _ => merged.push((v, w)),
+                }
+            }
+            *list = merged;
+        }
+        Weighted { adjacency }
+    }
+}
+
+/// Renumérote les communautés de 0 à k-1 dans l'ordre d'apparition, retourne k.
+fn renumber(partition: &mut [usize]) -> usize {
+    let mut index = vec![usize::MAX; partition.iter().max().map_or(0, |m| m + 1)];
+    let mut count = 0;
+    for c in partition.iter_mut() {
+        if index[*c] == usize::MAX {
+            index[*c] = count;
+            count += 1;
+        }
+        *c = index[*c];
+    }
+    count
+}
+
+impl Graph {
+    /// Modularité de `partition`, qui donne la communauté de chaque sommet: proportion des arêtes
+    /// internes aux communautés, moins cette proportion attendue si les arêtes étaient placées au
+    /// hasard en gardant les degrés. Les arêtes multiples et les boucles sont comptées. Échoue
+    /// si `partition` ne donne pas une communauté par sommet. Complexité: O(S+A).
+    /// ```
+    /// let g = graph::Graph::complete(4);
+    /// assert_eq!(Ok(0.0), g.modularity(&[0, 0, 0, 0]));
+    /// ```
+    pub fn modularity(&self, partition: &[usize]) -> Result<f64, String> {
+        self.modularity_with(partition, 1.0)
+    }
+    /// Modularité de `partition` où la proportion attendue des arêtes internes est multipliée par
+    /// `resolution`, l'objectif optimisé par `louvain`. Avec une résolution de 1, c'est
+    /// `modularity`.
+    pub fn modularity_with(&self, partition: &[usize], resolution: f64) -> Result<f64, String> {
+        if partition.len() != self.len() {
+            return Err(format!(
+                "The partition has {} nodes instead of {}",
+                partition.len(),
+                self.len()
+            ));
+        }
+        let total: usize = self.adjacency_list.iter().map(|c| c.len()).sum();
+        if total == 0 {
+            return Ok(0.0);
+        }
+        let count = partition.iter().max().map_or(0, |m| m + 1);
+        let mut internal = vec![0; count];
+        let mut degree = vec![0; count];
+        for (u, children) in self.adjacency_list.iter().enumerate() {
+            let c = partition[u];
+            degree[c] += children.len();
+            internal[c] += children.iter().filter(|&&v| partition[v] == c).count();
+        }
+        let total = total as f64;
+        Ok(internal
+            .iter()
+            .zip(degree.iter())
+            .map(|(&i, &d)| i as f64 / total - resolution * (d as f64 / total).powi(2))
+            .sum())
+    }
+    /// Détection de communautés par la méthode de Louvain: chaque sommet rejoint la communauté
+    /// voisine qui augmente le plus la modularité, puis les communautés sont fusionnées en un
+    /// graphe pondéré sur lequel l'opération est répétée tant qu'elle déplace des sommets. Une
+    /// `resolution` plus grande que 1 donne des communautés plus petites.
+    pub fn louvain(&self, resolution: f64) -> Louvain {
+        // Chaque arc a le poids 1/2 dans les deux sens, une boucle garde le poids 1: les poids
+        // sont symétriques même si un arc n'existe que dans un sens.
+        let mut adjacency: Vec<Vec<(usize, f64)>> = vec![Vec::new(); self.len()];
+        for (u, c) in self.adjacency_list.iter().enumerate() {
+            for &v in c.iter() {
+                if u == v {
+                    adjacency[u].push((u, 1.0));
+                } else {
+                    adjacency[u].push((v, 0.5));
+                    adjacency[v].push((u, 0.5));
+                }
+            }
+        }
+        let identity: Vec<usize> = (0..self.len()).collect();
+        let mut level = Weighted { adjacency }.aggregate(&identity, self.len());
+        let mut partition: Vec<usize> = (0..self.len()).collect();
+        let mut levels = Vec::new();
+        let mut modularity = Vec::new();
+        let q = |partition: &[usize]| {
+            self.modularity_with(partition, resolution)
+                .expect("one community per node")
+        };
+        loop {
+            let (mut community, moved) = level.local_moves(resolution);
+            if !moved {
+                break;
+            }
+            let count = renumber(&mut community);
+            partition.iter_mut().for_each(|c| *c = community[*c]);
+            modularity.push(q(&partition));
+            levels.push(partition.clone());
+            level = level.aggregate(&community, count);
+        }
+        if levels.is_empty() {
+            modularity.push(q(&partition));
+            levels.push(partition);
+        }
+        Louvain { levels, modularity }
+    }
+    /// Propagation asynchrone d'étiquettes: chaque sommet commence avec sa propre étiquette,
+    /// puis dans un ordre aléatoire prend l'étiquette la plus fréquente parmi ses voisins (au
+    /// hasard en cas d'égalité). S'arrête quand chaque sommet a une des étiquettes les plus
+    /// fréquentes de ses voisins. Retourne la partition, voir `Louvain`.
+    pub fn label_propagation<R: Rng + ?Sized>(&self, r: &mut R) -> Vec<usize> {
+        let n = self.len();
+        let adjacency = self.simple_adjacency();
+        let mut label: Vec<usize> = (0..n).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        let mut count = vec![0; n];
+        let mut best: Vec<usize> = Vec::new();
+
+        // Étiquettes les plus fréquentes parmi les voisins de u, placées dans best.
+        let mut frequent = |u: usize, label: &[usize], best: &mut Vec<usize>| {
+            best.clear();
+            let mut max = 0;
+            for &v in adjacency[u].iter() {
+                let l = label[v];
+                count[l] += 1;
+                if count[l] > max {
+                    max = count[l];
+                    best.clear();
+                }
+                if count[l] == max {
+                    best.push(l);
+                }
+            }
+            adjacency[u].iter().for_each(|&v| count[label[v]] = 0);
+        };
+
+        loop {
+            shuffle(r, &mut order);
+            for &u in order.iter() {
+                frequent(u, &label, &mut best);
+                if !best.is_empty() && !best.contains(&label[u]) {
+                    label[u] = best[below(r, best.len())];
+                }
+            }
+            let stable = (0..n).all(|u| {
+                frequent(u, &label, &mut best);
+                best.is_empty() || best.contains(&label[u])
+            });
+            if stable {
+                break;
+            }
+        }
+
+        renumber(&mut label);
+        label
+    }
+}
+#[test]
+fn test_modularity() {
+    // Deux cliques de 5 sommets reliées par une arête.
+    let mut g = Graph::complete(5);
+    for (a, b) in Graph::complete(5).edge_list().filter(|(a, b)| a < b) {
+        g.push((a + 5, b + 5));
+    }
+    g.push((4, 5));
+    let expected = vec![0, 0, 0, 0, 0, 1, 1, 1, 1, 1];
+    let q = g.modularity(&expected).unwrap();
+    assert!((q - (20.0 / 21.0 - 0.5)).abs() < 1e-12);
+    assert_eq!(Ok(0.0), g.modularity(&[0; 10]));
+    assert!(g.modularity(&[0; 9]).is_err());
+
+    let l = g.louvain(1.0);
+    assert_eq!(expected, l.partition());
+    assert_eq!(q, *l.modularity.last().unwrap());
+    assert_eq!(expected, g.label_propagation(&mut super::rng(0)));
+}
+#[test]
+fn test_louvain() {
+    let (g, membership) = Graph::gen_planted_partition(8, 30, 0.5, 0.01, &mut super::rng(1));
+    let l = g.louvain(1.0);
+    assert!(l.modularity.windows(2).all(|w| w[0] <= w[1] + 1e-12));
+    assert_eq!(l.levels.len(), l.modularity.len());
+    // Les communautés trouvées sont celles du modèle.
+    assert!(*l.modularity.last().unwrap() >= g.modularity(&membership).unwrap());
+    assert_eq!(8, l.partition().iter().max().unwrap() + 1);
+
+    // Une résolution plus grande donne plus de communautés.
+    let fine = g.louvain(20.0);
+    assert!(fine.partition().iter().max() > l.partition().iter().max());
+    let q = g.modularity_with(fine.partition(), 20.0).unwrap();
+    assert_eq!(q, *fine.modularity.last().unwrap());
+    assert!(fine.modularity.windows(2).all(|w| w[0] <= w[1] + 1e-12));
+
+    // Arcs dans un seul sens: les poids sont symétrisés et la méthode termine.
+    let directed = Graph::gen_gilbert(12, &mut super::rng(1));
+    let l = directed.louvain(1.0);
+    assert_eq!(12, l.partition().len());
+    assert!(l.modularity.windows(2).all(|w| w[0] <= w[1] + 1e-12));
+
+    let labels = g.label_propagation(&mut super::rng(2));
+    assert!(g.modularity(&labels).unwrap() > 0.7);
+}
@@ -0,0 +1,246 @@
+use super::Graph;
+
+/// Effectifs des couples de communautés, puis des communautés de chaque partition.
+type Contingency = (Vec<usize>, Vec<usize>, Vec<usize>);
+
+/// Table de contingence de deux partitions: le nombre de sommets de chaque couple de
+/// communautés non vide, puis la taille des communautés de chaque partition. Échoue si les
+/// partitions n'ont pas le même nombre de sommets.
+fn contingency(a: &[usize], b: &[usize]) -> Result<Contingency, String> {
+    if a.len() != b.len() {
+        return Err(format!(
+            "The partitions have {} and {} nodes",
+            a.len(),
+            b.len()
+        ));
+    }
+    let mut pairs: Vec<(usize, usize)> = a.iter().copied().zip(b.iter().copied()).collect();
+    pairs.sort_unstable();
+    let mut joint = Vec::new();
+    let mut i = 0;
+    while i < pairs.len() {
+        let j = i + pairs[i..].iter().take_while(|&&p| p == pairs[i]).count();
+        joint.push(j - i);
+        i = j;
+    }
+    let sizes = |p: &[usize]| {
+        let mut s = vec![0; p.iter().max().map_or(0, |m| m + 1)];
+        p.iter().for_each(|&c| s[c] += 1);
+        s.retain(|&x| x > 0);
+        s
+    };
+    Ok((joint, sizes(a), sizes(b)))
+}
+
+/// Entropie (en nats) d'une distribution donnée par des effectifs de total `n`.
+fn entropy(counts: &[usize], n: f64) -> f64 {
+    counts
+        .iter()
+        .map(|&c| c as f64 / n)
+        .map(|p| -p * p.ln())
+        .sum()
+}
+
+/// Entropies de `a`, de `b` et information mutuelle des deux partitions.
+fn information(a: &[usize], b: &[usize]) -> Result<(f64, f64, f64), String> {
+    let n = a.len() as f64;
+    let (joint, size_a, size_b) = contingency(a, b)?;
+    let (ha, hb) = (entropy(&size_a, n), entropy(&size_b, n));
+    Ok((ha, hb, ha + hb - entropy(&joint, n)))
+}
+
+/// Information mutuelle normalisée de deux partitions, qui donnent la communauté de chaque
+/// sommet: l'information mutuelle divisée par la moyenne des entropies. Vaut 1 pour deux
+/// partitions identiques à la numérotation près. Échoue si les partitions n'ont pas le même
+/// nombre de sommets. Complexité: O(S log S).
+/// ```
+/// assert_eq!(Ok(1.0), graph::nmi(&[0, 0, 1, 1], &[1, 1, 0, 0]));
+/// ```
+pub fn nmi(a: &[usize], b: &[usize]) -> Result<f64, String> {
+    let (ha, hb, mutual) = information(a, b)?;
+    if ha + hb == 0.0 {
+        return Ok(1.0);
+    }
+    Ok((2.0 * mutual / (ha + hb)).clamp(0.0, 1.0))
+}
+
+/// Variation d'information de deux partitions: `H(a) + H(b) - 2 I(a, b)`, en nats. C'est une
+/// distance, nulle pour deux partitions identiques. Échoue comme `nmi`. Complexité: O(S log S).
+pub fn variation_of_information(a: &[usize], b: &[usize]) -> Result<f64, String> {
+    let (ha, hb, mutual) = information(a, b)?;
+    Ok((ha + hb - 2.0 * mutual).max(0.0))
+}
+
+/// Indice de Rand ajusté de deux partitions: proportion des paires de sommets classées de la
+/// même manière, corrigée de sorte qu'elle vaille 0 en moyenne pour des partitions aléatoires et
+/// 1 pour des partitions identiques. Échoue comme `nmi`. Complexité: O(S log S).
+pub fn adjusted_rand(a: &[usize], b: &[usize]) -> Result<f64, String> {
+    let (joint, size_a, size_b) = contingency(a, b)?;
+    let pairs = |counts: &[usize]| -> f64 {
+        counts
+            .iter()
+            .map(|&c| (c * c.saturating_sub(1) / 2) as f64)
+            .sum()
+    };
+    let total = pairs(&[a.len()]);
+    let (index, pa, pb) = (pairs(&joint), pairs(&size_a), pairs(&size_b));
+    let expected = if total > 0.0 { pa * pb / total } else { 0.0 };
+    let maximum = (pa + pb) / 2.0;
+    if maximum == expected {
+        return Ok(1.0);
+    }
+    Ok((index - expected) / (maximum - expected))
+}
+
+/// Qualité d'une communauté mesurée sur le graphe, calculée par `Graph::cluster_quality`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusterQuality {
+    /// Nombre de sommets.
+    pub size: usize,
+    /// Nombre d'arêtes entre deux sommets de la communauté.
+    pub internal: usize,
+    /// Nombre d'arêtes entre la communauté et le reste du graphe.
+    pub cut: usize,
+    /// Somme des degrés des sommets.
+    pub volume: usize,
+    /// Coupe divisée par le plus petit volume de la communauté et du reste du graphe, 0 si
+    /// celui-ci est nul.
+    pub conductance: f64,
+    /// Arêtes internes divisées par le nombre de paires de sommets, 0 pour moins de 2 sommets.
+    pub density: f64,
+}
+
+impl Graph {
+    /// Mesure chaque communauté de `partition`, qui donne la communauté de chaque sommet. Le
+    /// résultat est indexé par le numéro de communauté. Les boucles et les arêtes multiples sont
+    /// ignorées. Échoue si `partition` ne donne pas une communauté par sommet.
+    /// Complexité: O(S+A).
+    /// ```
+    /// let q = graph::Graph::path(4).cluster_quality(&[0, 0, 1, 1]).unwrap();
+    /// assert_eq!(1, q[0].cut);
+    /// assert_eq!(1.0 / 3.0, q[0].conductance);
+    /// ```
+    pub fn cluster_quality(&self, partition: &[usize]) -> Result<Vec<ClusterQuality>, String> {
+        if partition.len() != self.len() {
+            return Err(format!(
+                "The partition has {} nodes instead of {}",
+                partition.len(),
+                self.len()
+            ));
+        }
+        let count = partition.iter().max().map_or(0, |m| m + 1);
+        let mut size = vec![0usize; count];
+        let mut internal = vec![0; count];
+        let mut cut = vec![0; count];
+        let mut volume = vec![0; count];
+        for (u, children) in self.simple_adjacency().iter().enumerate() {
+            let c = partition[u];
+            size[c] += 1;
+            volume[c] += children.len();
+            for &v in children.iter() {
+                if partition[v] != c {
+                    cut[c] += 1;
+                } else if u < v {
+                    internal[c] += 1;
+                }
+            }
+        }
+
+        let total: usize = volume.iter().sum();
+        Ok((0..count)
+            .map(|c| {
+                let smallest = volume[c].min(total - volume[c]);
+                let pairs = size[c] * size[c].saturating_sub(1) / 2;
+                ClusterQuality {
+                    size: size[c],
+                    internal: internal[c],
+                    cut: cut[c],
+                    volume: volume[c],
+                    conductance: if smallest > 0 {
+                        cut[c] as f64 / smallest as f64
+                    } else {
+                        0.0
+                    },
+                    density: if pairs > 0 {
+                        internal[c] as f64 / pairs as f64
+                    } else {
+                        0.0
+                    },
+                }
+            })
+            .collect())
+    }
+}
+#[test]
+fn test_compare() {
+    let a = [0, 0, 0, 1, 1, 1];
+    let b = [5, 5, 5, 2, 2, 2];
+    assert_eq!(1.0, nmi(&a, &b).unwrap());
+    assert_eq!(1.0, adjusted_rand(&a, &b).unwrap());
+    assert_eq!(0.0, variation_of_information(&a, &b).unwrap());
+
+    // Partitions indépendantes.
+    let c = [0, 1, 0, 1, 0, 1];
+    let d = [0, 0, 1, 1, 2, 2];
+    assert!(nmi(&[0, 0, 1, 1], &[0, 1, 0, 1]).unwrap().abs() < 1e-12);
+    assert!(
+        (variation_of_information(&[0, 0, 1, 1], &[0, 1, 0, 1]).unwrap() - 2.0 * 2f64.ln()).abs()
+            < 1e-12
+    );
+
+    // Valeurs de référence de scikit-learn.
+    assert!(
+        (adjusted_rand(&[0, 0, 1, 1], &[0, 0, 1, 2]).unwrap() - 0.5714285714285715).abs() < 1e-12
+    );
+    assert!((nmi(&[0, 0, 1, 1], &[0, 0, 1, 2]).unwrap() - 0.8).abs() < 1e-12);
+    assert!(adjusted_rand(&c, &d).unwrap() < 0.0);
+    assert_eq!(1.0, nmi(&[0; 4], &[3; 4]).unwrap());
+
+    // Partitions de tailles différentes.
+    let error = Err("The partitions have 4 and 3 nodes".to_string());
+    assert_eq!(error, nmi(&[0; 4], &[0; 3]));
+    assert_eq!(error, adjusted_rand(&[0; 4], &[0; 3]));
+    assert_eq!(error, variation_of_information(&[0; 4], &[0; 3]));
+
+    // Les communautés du modèle sont retrouvées par Louvain.
+    let (g, membership) = Graph::gen_planted_partition(4, 25, 0.6, 0.01, &mut super::rng(3));
+    let found = g.louvain(1.0);
+    assert!(nmi(&membership, found.partition()).unwrap() > 0.95);
+    assert!(adjusted_rand(&membership, found.partition()).unwrap() > 0.95);
+}
+#[test]
+fn test_cluster_quality() {
+    // Deux triangles reliés par l'arête 2-3, avec une arête multiple et une boucle.
+    let mut g = Graph::new(Some(6));
+    for &e in [
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (5, 3),
+        (0, 1),
+        (5, 5),
+    ]
+    .iter()
+    {
+        g.add(e);
+    }
+    let q = g.cluster_quality(&[0, 0, 0, 1, 1, 1]).unwrap();
+    let expected = ClusterQuality {
+        size: 3,
+        internal: 3,
+        cut: 1,
+        volume: 7,
+        conductance: 1.0 / 7.0,
+        density: 1.0,
+    };
+    assert_eq!(vec![expected.clone(), expected], q);
+
+    let q = g.cluster_quality(&[0, 0, 0, 0, 0, 1]).unwrap();
+    assert_eq!(2, q[1].cut);
+    assert_eq!(1.0, q[1].conductance);
+    assert_eq!(0.0, q[1].density);
+    assert!(g.cluster_quality(&[0; 5]).is_err());
+}
@@ -0,0 +1,171 @@
+use super::Graph;
+
+/// Distribution des distances entre les paires de sommets de la plus grande composante connexe,
+/// calculée par `Graph::distance_distribution`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceDistribution {
+    /// Nombre de sommets de la plus grande composante connexe.
+    pub component: usize,
+    /// Nombre de paires de sommets distincts à chaque distance. Longueur = diamètre+1
+    pub histogram: Vec<usize>,
+    /// Longueur caractéristique: la distance moyenne entre deux sommets distincts, 0 s'il n'y a
+    /// qu'un sommet.
+    pub average: f64,
+    /// Diamètre effectif: la distance sous laquelle se trouvent 90% des paires, interpolée
+    /// linéairement entre deux distances entières.
+    pub effective_diameter: f64,
+}
+
+impl DistanceDistribution {
+    /// Plus grande distance entre deux sommets de la composante.
+    pub fn diameter(&self) -> usize {
+        self.histogram.len().saturating_sub(1)
+    }
+    /// Nombre total de paires de sommets distincts.
+    pub fn pairs(&self) -> usize {
+        self.histogram.iter().sum()
+    }
+    /// Distance sous laquelle se trouve la proportion `q` (entre 0 et 1) des paires, interpolée
+    /// linéairement entre deux distances entières.
+    pub fn percentile(&self, q: f64) -> f64 {
+        let target = q * self.pairs() as f64;
+        let mut below = 0;
+        for (d, &nb) in self.histogram.iter().enumerate().skip(1) {
+            if nb > 0 && (below + nb) as f64 >= target {
+                return (d - 1) as f64 + (target - below as f64) / nb as f64;
+            }
+            below += nb;
+        }
+        self.diameter() as f64
+    }
+}
+
+impl Graph {
+    /// Sommets de la plus grande composante connexe.
+    fn largest_component(&self) -> Vec<bool> {
+        let everything = vec![true; self.len()];
+        let mut component = vec![usize::MAX; self.len()];
+        let (mut best, mut best_size) = (usize::MAX, 0);
+        for n in 0..self.len() {
+            if component[n] != usize::MAX {
+                continue;
+            }
+            let mut size = 0;
+            self.bfs(n, &everything, &mut |m, _| {
+                component[m] = n;
+                size += 1;
+            });
+            if size > best_size {
+                best = n;
+                best_size = size;
+            }
+        }
+        component.iter().map(|&c| c == best).collect()
+    }
+    /// Distribution exacte des distances dans la plus grande composante connexe: un parcours
+    /// en largeur depuis chaque sommet, répartis entre les cœurs du processeur. Contrairement à
+    /// `Stats::distance`, toutes les paires sont mesurées. Complexité: O(S*(S+A)).
+    /// ```
+    /// let d = graph::Graph::path(5).distance_distribution();
+    /// assert_eq!(vec![0, 4, 3, 2, 1], d.histogram);
+    /// assert_eq!(2.0, d.average);
+    /// ```
+    pub fn distance_distribution(&self) -> DistanceDistribution {
+        let keep = self.largest_component();
+        let sources: Vec<usize> = (0..self.len()).filter(|&n| keep[n]).collect();
+        let threads = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(sources.len())
+            .max(1);
+        let chunk = sources.len().div_ceil(threads).max(1);
+
+        // Chaque paire est vue depuis ses deux extrémités.
+        let mut histogram: Vec<usize> = Vec::new();
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = sources
+                .chunks(chunk)
+                .map(|origins| {
+                    let keep = &keep;
+                    scope.spawn(move || {
+                        let mut local: Vec<usize> = Vec::new();
+                        for &origin in origins {
+                            self.bfs(origin, keep, &mut |_, d| {
+                                if local.len() <= d {
+                                    local.resize(d + 1, 0);
+                                }
+                                local[d] += 1;
+                            });
+                        }
+                        local
+                    })
+                })
+                .collect();
+            for worker in workers {
+                let local = worker.join().expect("BFS thread panicked");
+                if histogram.len() < local.len() {
+                    histogram.resize(local.len(), 0);
+                }
+                histogram.iter_mut().zip(local).for_each(|(h, x)| *h += x);
+            }
+        });
+        if let Some(zero) = histogram.first_mut() {
+            *zero = 0;
+        }
+        histogram.iter_mut().for_each(|h| *h /= 2);
+
+        let pairs: usize = histogram.iter().sum();
+        let total: usize = histogram.iter().enumerate().map(|(d, &nb)| d * nb).sum();
+        let mut distribution = DistanceDistribution {
+            component: sources.len(),
+            histogram,
+            average: if pairs == 0 {
+                0.0
+            } else {
+                total as f64 / pairs as f64
+            },
+            effective_diameter: 0.0,
+        };
+        distribution.effective_diameter = distribution.percentile(0.9);
+        distribution
+    }
+}
+#[test]
+fn test_distance_distribution() {
+    let d = Graph::path(5).distance_distribution();
+    assert_eq!(4, d.diameter());
+    assert_eq!(10, d.pairs());
+    assert!((d.effective_diameter - 3.0).abs() < 1e-9);
+    assert!((d.percentile(0.5) - 4.0 / 3.0).abs() < 1e-9);
+
+    // Comparaison avec un parcours en largeur depuis chaque sommet de la plus grande
+    // composante, sur un graphe non connexe.
+    let g = Graph::gen_geometric(300, 0.08, false, &mut super::rng(3)).0;
+    let d = g.distance_distribution();
+    let keep = g.largest_component();
+    let mut expected = vec![0; d.histogram.len()];
+    for u in (0..g.len()).filter(|&u| keep[u]) {
+        for (v, dist) in g.bfs(u, &keep, &mut |_, _| {}).iter().enumerate() {
+            if let Some(dist) = dist {
+                if v > u {
+                    expected[*dist] += 1;
+                }
+            }
+        }
+    }
+    assert!(d.component < g.len());
+    assert_eq!(d.component, keep.iter().filter(|&&k| k).count());
+    assert_eq!(expected, d.histogram);
+    assert_eq!(d.component * (d.component - 1) / 2, d.pairs());
+
+    let s = Graph::petersen().stats_with(&super::StatsOptions {
+        distances: true,
+        ..super::StatsOptions::default()
+    });
+    let d = s.distances.unwrap();
+    assert_eq!(vec![0, 15, 30], d.histogram);
+    assert_eq!(s.distance, d.diameter());
+    assert_eq!(75.0 / 45.0, d.average);
+
+    let d = Graph::new(Some(1)).distance_distribution();
+    assert_eq!((1, 0, 0.0), (d.component, d.pairs(), d.average));
+}
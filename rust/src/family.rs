@@ -0,0 +1,139 @@
+use super::Graph;
+
+impl Graph {
+    /// Chemin de `size` sommets: `0 - 1 - ... - size-1`. Diamètre: `size-1`.
+    pub fn path(size: usize) -> Graph {
+        Graph::new_iter((1..size).map(|i| (i - 1, i)), Some(size))
+    }
+    /// Cycle de `size` sommets. Diamètre: `size/2`.
+    pub fn cycle(size: usize) -> Graph {
+        let mut g = Graph::path(size);
+        if size > 2 {
+            g.add((size - 1, 0));
+        }
+        g
+    }
+    /// Étoile de `size` sommets: le sommet 0 est relié à tous les autres. Diamètre: 2.
+    pub fn star(size: usize) -> Graph {
+        Graph::new_iter((1..size).map(|i| (0, i)), Some(size))
+    }
+    /// Roue de `size` sommets: un cycle des sommets `1..size` dont tous les sommets sont reliés
+    /// au sommet 0. Diamètre: 2 si `size > 4`.
+    pub fn wheel(size: usize) -> Graph {
+        let mut g = Graph::star(size);
+        if size > 3 {
+            (2..size).for_each(|i| g.add((i - 1, i)));
+            g.add((size - 1, 1));
+        }
+        g
+    }
+    /// Graphe complet de `size` sommets. Diamètre: 1.
+    pub fn complete(size: usize) -> Graph {
+        Graph::new_iter(
+            (0..size).flat_map(|a| (0..a).map(move |b| (b, a))),
+            Some(size),
+        )
+    }
+    /// Graphe biparti complet: les sommets `0..a` sont reliés aux sommets `a..a+b`. Diamètre: 2.
+    pub fn complete_bipartite(a: usize, b: usize) -> Graph {
+        Graph::new_iter(
+            (0..a).flat_map(|i| (a..a + b).map(move |j| (i, j))),
+            Some(a + b),
+        )
+    }
+    /// Grille dont chaque dimension a la longueur donnée par `dims` (2 dimensions pour une grille
+    /// plane, 3 pour un cube). Le sommet de coordonnées `(x, y, z)` a pour indice
+    /// `x + dims[0]*(y + dims[1]*z)`. Diamètre: `Σ(dims[i]-1)`.
+    pub fn grid(dims: &[usize]) -> Graph {
+        Graph::lattice(dims, false)
+    }
+    /// Tore: une grille dont les bords opposés sont reliés, voir `grid`. Les dimensions de
+    /// longueur 1 ou 2 ne sont pas refermées. Diamètre: `Σ(dims[i]/2)`.
+    pub fn torus(dims: &[usize]) -> Graph {
+        Graph::lattice(dims, true)
+    }
+    fn lattice(dims: &[usize], wrap: bool) -> Graph {
+        let size = dims.iter().product();
+        let mut g = Graph::new(Some(size));
+
+        for n in 0..size {
+            // Écart entre deux sommets voisins le long de la dimension courante.
+            let mut step = 1;
+            for &d in dims {
+                let x = (n / step) % d;
+                if x + 1 < d {
+                    g.add((n, n + step));
+                } else if wrap && d > 2 {
+                    g.add((n, n - x * step));
+                }
+                step *= d;
+            }
+        }
+
+        g
+    }
+    /// Hypercube de dimension `dim`: `2^dim` sommets, reliés si leurs indices diffèrent d'un seul
+    /// bit. Diamètre: `dim`.
+    pub fn hypercube(dim: usize) -> Graph {
+        let size = 1 << dim;
+        Graph::new_iter(
+            (0..size).flat_map(|n| {
+                (0..dim)
+                    .map(move |b| (n, n | 1 << b))
+                    .filter(|(a, b)| a != b)
+            }),
+            Some(size),
+        )
+    }
+    /// Arbre `k`-aire équilibré de profondeur `depth`; la racine est le sommet 0 et les enfants du
+    /// sommet `n` sont `k*n+1..=k*n+k`. Diamètre: `2*depth` si `k > 1`.
+    pub fn kary_tree(k: usize, depth: usize) -> Graph {
+        // Nombre de sommets: 1 + k + k² + ... + k^depth.
+        let size = (0..=depth)
+            .fold((0, 1), |(sum, pow), _| (sum + pow, pow * k))
+            .0;
+        Graph::new_iter((1..size).map(|n| ((n - 1) / k, n)), Some(size))
+    }
+    /// Graphe de Petersen: 10 sommets, 15 arêtes. Diamètre: 2.
+    pub fn petersen() -> Graph {
+        let mut g = Graph::new(Some(10));
+        for i in 0..5 {
+            g.add((i, (i + 1) % 5));
+            g.add((i, i + 5));
+            g.add((i + 5, (i + 2) % 5 + 5));
+        }
+        g
+    }
+}
+#[test]
+fn family_distance() {
+    assert_eq!(0, Graph::path(1).distance());
+    assert_eq!(9, Graph::path(10).distance());
+    assert_eq!(5, Graph::cycle(10).distance());
+    assert_eq!(5, Graph::cycle(11).distance());
+    assert_eq!(2, Graph::star(10).distance());
+    assert_eq!(2, Graph::wheel(10).distance());
+    assert_eq!(1, Graph::complete(10).distance());
+    assert_eq!(2, Graph::complete_bipartite(3, 4).distance());
+    assert_eq!(4 + 6, Graph::grid(&[5, 7]).distance());
+    assert_eq!(2 + 3 + 1, Graph::grid(&[3, 4, 2]).distance());
+    assert_eq!(2 + 3, Graph::torus(&[5, 7]).distance());
+    assert_eq!(1 + 2 + 1, Graph::torus(&[3, 4, 2]).distance());
+    assert_eq!(5, Graph::hypercube(5).distance());
+    assert_eq!(8, Graph::kary_tree(3, 4).distance());
+    assert_eq!(2, Graph::petersen().distance());
+}
+#[test]
+fn family_edges() {
+    assert_eq!(45, Graph::complete(10).edges());
+    assert_eq!(12, Graph::complete_bipartite(3, 4).edges());
+    assert_eq!(2 * 35, Graph::torus(&[5, 7]).edges());
+    assert_eq!(5 * 16, Graph::hypercube(5).edges());
+    assert_eq!(120, Graph::kary_tree(3, 4).edges());
+    assert_eq!(18, Graph::wheel(10).edges());
+    assert_eq!(15, Graph::petersen().edges());
+    assert!(Graph::petersen()
+        .adjacency_list
+        .iter()
+        .all(|c| c.len() == 3));
+}
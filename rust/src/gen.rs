@@ -0,0 +1,559 @@
+use super::Graph;
+use rand::{Rng, SeedableRng};
+
+/// Générateur pseudo-aléatoire utilisé avec une graine. PCG 64 bits produit la même suite sur
+/// toutes les plates-formes et ne dépend pas de la version de `rand`.
+pub type SeedRng = rand_pcg::Pcg64;
+
+/// Crée le générateur pseudo-aléatoire reproductible associé à la graine `seed`.
+pub fn rng(seed: u64) -> SeedRng {
+    SeedRng::seed_from_u64(seed)
+}
+
+/// Tire un entier uniforme dans `0..n`. Le tirage se fait sur 64 bits pour que le résultat ne
+/// dépende pas de la taille de `usize`.
+pub(crate) fn below<R: Rng + ?Sized>(r: &mut R, n: usize) -> usize {
+    r.gen_range(0, n as u64) as usize
+}
+
+/// Nombre d'échecs avant le prochain succès d'épreuves de probabilité `p` (loi géométrique).
+/// Permet de sauter directement à la prochaine arête présente au lieu de tirer chaque paire.
+pub(crate) fn skip<R: Rng + ?Sized>(r: &mut R, p: f64) -> usize {
+    if p >= 1.0 {
+        return 0;
+    } else if p <= 0.0 {
+        return usize::MAX;
+    }
+    let x: f64 = r.gen();
+    ((1.0 - x).ln() / (1.0 - p).ln()).floor() as usize
+}
+
+/// Convertit l'indice linéaire `i` d'une paire `(x, y)` avec `x < y` en la paire, les paires
+/// étant rangées par `y` puis `x` croissants.
+pub(crate) fn triangular(i: usize) -> (usize, usize) {
+    let mut y = ((1.0 + (1.0 + 8.0 * i as f64).sqrt()) / 2.0) as usize;
+    // Corrige les erreurs d'arrondi pour les grands indices.
+    while y * (y - 1) / 2 > i {
+        y -= 1;
+    }
+    while (y + 1) * y / 2 <= i {
+        y += 1;
+    }
+    (i - y * (y - 1) / 2, y)
+}
+
+/// Mélange de Fisher-Yates, reproductible sur toutes les plates-formes grâce à `below`.
+pub(crate) fn shuffle<T, R: Rng + ?Sized>(r: &mut R, v: &mut [T]) {
+    for i in (1..v.len()).rev() {
+        v.swap(i, below(r, i + 1));
+    }
+}
+
+impl Graph {
+    /// Génère un graphe avec `f` à partir du générateur associé à la graine `seed`. La graine est
+    /// retenue dans le graphe pour être enregistrée par `save`.
+    /// ```
+    /// use graph::Graph;
+    /// let a = Graph::with_seed(42, |r| Graph::gen_barabasi_albert(100, r));
+    /// let b = Graph::with_seed(42, |r| Graph::gen_barabasi_albert(100, r));
+    /// assert_eq!(Some(42), a.seed());
+    /// assert!(a.edge_list().eq(b.edge_list()));
+    /// ```
+    pub fn with_seed<F: FnOnce(&mut SeedRng) -> Graph>(seed: u64, f: F) -> Graph {
+        let mut g = f(&mut rng(seed));
+        g.seed = Some(seed);
+        g
+    }
+    /// Génération de graphe avec le modèle d'Edgar Gilbert: chaque paire ordonnée `(i, j)`, boucles
+    /// comprises, est tirée avec la probabilité 1/2 et seul `j` est ajouté aux voisins de `i`.
+    pub fn gen_gilbert<R: Rng + ?Sized>(size: usize, r: &mut R) -> Graph {
+        let mut g = Graph::new(Some(size));
+        Graph::stream_gilbert(size, r).for_each(|(i, j)| g.adjacency_list[i].push(j));
+        g
+    }
+    /// Arcs du graphe de Gilbert dans l'ordre de `edge_list`, avec les mêmes tirages que
+    /// `gen_gilbert`, boucles et arcs `(j, i)` compris. La mémoire utilisée est constante. Pour
+    /// écrire le même fichier que `Graph::save`, seuls les arcs `(i, j)` avec `i < j` sont gardés:
+    /// ```
+    /// let name = std::env::temp_dir().join("stream_gilbert.csv");
+    /// let name = name.to_str().unwrap();
+    /// let mut r = graph::rng(1);
+    /// let edges = graph::Graph::stream_gilbert(100, &mut r).filter(|(i, j)| i < j);
+    /// graph::save_edges(name, edges, Some(1)).unwrap();
+    /// # std::fs::remove_file(name).unwrap();
+    /// ```
+    pub fn stream_gilbert<'a, R: Rng + ?Sized>(
+        size: usize,
+        r: &'a mut R,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        (0..size)
+            .flat_map(move |i| (0..size).map(move |j| (i, j)))
+            .filter(move |_| r.gen::<bool>())
+    }
+    /// Génère un graphe de Barabàsi-Albert à partir d'un triangle, chaque nouveau sommet ajoute
+    /// deux arêtes. Voir `gen_preferential` pour les autres paramètres.
+    pub fn gen_barabasi_albert<R: Rng + ?Sized>(size: usize, r: &mut R) -> Graph {
+        Graph::gen_preferential(None, size, &Preferential::default(), r)
+    }
+    /// Arêtes du graphe de Barabàsi-Albert, voir `gen_barabasi_albert` et `stream_preferential`.
+    pub fn stream_barabasi_albert<'a, R: Rng + ?Sized>(
+        size: usize,
+        r: &'a mut R,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        Graph::stream_preferential(None, size, &Preferential::default(), r)
+    }
+    /// Agrandit le graphe `initial` jusqu'à `size` sommets par attachement préférentiel; par
+    /// défaut `initial` est une clique de `m+1` sommets. Comme chaque sommet est présent dans une
+    /// liste autant de fois que son degré, un tirage uniforme dans cette liste donne un sommet
+    /// proportionnellement à son degré. Complexité temporelle et mémoire linéaire en `size*m`
+    /// (en moyenne si `alpha` est différent de 1).
+    /// ```
+    /// use graph::{Graph, Preferential};
+    /// let param = Preferential {
+    ///     m: 3,
+    ///     distinct: true,
+    ///     triad: 0.5,
+    ///     ..Preferential::default()
+    /// };
+    /// let g = Graph::with_seed(1, |r| Graph::gen_preferential(None, 100, &param, r));
+    /// assert_eq!(6 + 96 * 3, g.edges());
+    /// ```
+    pub fn gen_preferential<R: Rng + ?Sized>(
+        initial: Option<Graph>,
+        size: usize,
+        param: &Preferential,
+        r: &mut R,
+    ) -> Graph {
+        let mut g = initial.unwrap_or_else(|| Graph::complete(param.m + 1));
+        if size <= g.len() {
+            return g;
+        }
+        let edges = PreferentialEdges::new(&g, size, param, r);
+        g.adjacency_list.resize_with(size, Vec::new);
+        edges.for_each(|e| g.add(e));
+        g
+    }
+    /// Arêtes du graphe généré par `gen_preferential`: d'abord celles du graphe initial (sans ses
+    /// boucles), puis celles ajoutées. Au lieu des listes de voisins, seules les deux extrémités de
+    /// chaque arête ajoutée sont conservées (sauf avec la formation de triades).
+    pub fn stream_preferential<'a, R: Rng + ?Sized>(
+        initial: Option<&Graph>,
+        size: usize,
+        param: &Preferential,
+        r: &'a mut R,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let complete;
+        let initial = match initial {
+            Some(g) => g,
+            None => {
+                complete = Graph::complete(param.m + 1);
+                &complete
+            }
+        };
+        let first: Vec<(usize, usize)> = initial.edge_list().filter(|(a, b)| a < b).collect();
+        first
+            .into_iter()
+            .chain(PreferentialEdges::new(initial, size, param, r))
+    }
+    /// Génère un graphe petit monde de Watts-Strogatz: un anneau de `size` sommets, chacun relié
+    /// à ses `k` plus proches voisins (`k/2` de chaque côté), dont chaque arête est reliée à un
+    /// autre sommet choisi au hasard avec la probabilité `beta`. Le nombre d'arêtes est conservé,
+    /// sans boucle ni arête multiple. Il n'y a pas de version `stream_`: chaque arête déplacée
+    /// dépend de toutes celles déjà placées, il faudrait donc garder le graphe entier en mémoire.
+    pub fn gen_watts_strogatz<R: Rng + ?Sized>(
+        size: usize,
+        k: usize,
+        beta: f64,
+        r: &mut R,
+    ) -> Graph {
+        let mut g = Graph::ring_lattice(size, k);
+
+        for j in 1..=k.min(size.saturating_sub(1)) / 2 {
+            for i in 0..size {
+                if g.adjacency_list[i].len() + 1 >= size || r.gen::<f64>() >= beta {
+                    continue;
+                }
+                let w = g.random_non_neighbour(i, r);
+                let v = (i + j) % size;
+                g.remove((i, v));
+                g.add((i, w));
+            }
+        }
+
+        g
+    }
+    /// Génère un graphe petit monde de Newman-Watts: comme le modèle de Watts-Strogatz, mais au
+    /// lieu d'être déplacée, chaque arête de l'anneau ajoute avec la probabilité `beta` un
+    /// raccourci vers un sommet choisi au hasard. Le graphe reste donc connexe. Comme pour
+    /// `gen_watts_strogatz`, il n'y a pas de version `stream_`: un raccourci ne doit doubler ni
+    /// une arête de l'anneau ni un raccourci précédent, qu'il faudrait tous garder en mémoire.
+    pub fn gen_newman_watts<R: Rng + ?Sized>(size: usize, k: usize, beta: f64, r: &mut R) -> Graph {
+        let mut g = Graph::ring_lattice(size, k);
+
+        for _ in 1..=k.min(size.saturating_sub(1)) / 2 {
+            for i in 0..size {
+                if g.adjacency_list[i].len() + 1 >= size || r.gen::<f64>() >= beta {
+                    continue;
+                }
+                let w = g.random_non_neighbour(i, r);
+                g.add((i, w));
+            }
+        }
+
+        g
+    }
+    /// Anneau de `size` sommets où chaque sommet est relié à ses `k` plus proches voisins. `k`
+    /// est arrondi au nombre pair inférieur et limité à `size-1`.
+    fn ring_lattice(size: usize, k: usize) -> Graph {
+        let mut g = Graph::new(Some(size));
+        for j in 1..=k.min(size.saturating_sub(1)) / 2 {
+            for i in 0..size {
+                g.add((i, (i + j) % size));
+            }
+        }
+        g
+    }
+    /// Tire uniformément un sommet différent de `n` et qui ne lui est pas relié. Au moins un tel
+    /// sommet doit exister.
+    fn random_non_neighbour<R: Rng + ?Sized>(&self, n: usize, r: &mut R) -> usize {
+        loop {
+            let w = below(r, self.len());
+            if w != n && !self.adjacency_list[n].contains(&w) {
+                return w;
+            }
+        }
+    }
+    /// Retire une arête entre `a` et `b` si elle existe.
+    fn remove(&mut self, (a, b): (usize, usize)) {
+        if let Some(i) = self.adjacency_list[a].iter().position(|&c| c == b) {
+            self.adjacency_list[a].swap_remove(i);
+        }
+        if let Some(i) = self.adjacency_list[b].iter().position(|&c| c == a) {
+            self.adjacency_list[b].swap_remove(i);
+        }
+    }
+}
+
+/// Paramètres de l'attachement préférentiel, voir `Graph::gen_preferential`.
+#[derive(Debug, Clone)]
+pub struct Preferential {
+    /// Nombre d'arêtes ajoutées avec chaque nouveau sommet.
+    pub m: usize,
+    /// Interdit de relier deux fois le nouveau sommet au même sommet.
+    pub distinct: bool,
+    /// Probabilité de formation d'une triade (modèle de Holme-Kim): après un attachement
+    /// préférentiel, l'arête suivante est reliée à un voisin du sommet choisi.
+    pub triad: f64,
+    /// Exposant de l'attachement préférentiel non linéaire: un sommet est choisi avec une
+    /// probabilité proportionnelle à son degré puissance `alpha`.
+    pub alpha: f64,
+}
+
+impl Default for Preferential {
+    fn default() -> Preferential {
+        Preferential {
+            m: 2,
+            distinct: false,
+            triad: 0.0,
+            alpha: 1.0,
+        }
+    }
+}
+
+/// Itérateur des arêtes ajoutées par l'attachement préférentiel, voir
+/// `Graph::gen_preferential`. La liste des sommets répétés autant de fois que leur degré n'est pas
+/// construite: ses premiers éléments sont les demi-arêtes du graphe initial, puis chaque arête
+/// ajoutée y place sa source et sa cible.
+struct PreferentialEdges<'a, R: ?Sized> {
+    r: &'a mut R,
+    param: Preferential,
+    size: usize,
+    /// Prochain sommet à ajouter.
+    i: usize,
+    /// Demi-arêtes du graphe initial.
+    stubs: Vec<usize>,
+    /// Cible de chaque arête ajoutée.
+    targets: Vec<usize>,
+    /// Source de chaque arête ajoutée.
+    sources: Vec<usize>,
+    degree: Vec<usize>,
+    /// Voisins de chaque sommet, seulement pour la formation de triades.
+    neighbours: Vec<Vec<usize>>,
+    /// Nombre de sommets de degré non nul.
+    present: usize,
+    degree_max: usize,
+    /// Indice dans `targets` de la prochaine arête à retourner.
+    next: usize,
+}
+
+impl<'a, R: Rng + ?Sized> PreferentialEdges<'a, R> {
+    fn new(initial: &Graph, size: usize, param: &Preferential, r: &'a mut R) -> Self {
+        let degree: Vec<usize> = initial.adjacency_list.iter().map(|c| c.len()).collect();
+        let mut stubs: Vec<usize> = Vec::with_capacity(degree.iter().sum());
+        for (n, &d) in degree.iter().enumerate() {
+            stubs.extend(std::iter::repeat_n(n, d));
+        }
+        let added = size.saturating_sub(initial.len());
+
+        PreferentialEdges {
+            r,
+            param: param.clone(),
+            size,
+            i: initial.len(),
+            stubs,
+            targets: Vec::with_capacity(added * param.m),
+            sources: Vec::with_capacity(added * param.m),
+            present: degree.iter().filter(|&&d| d > 0).count(),
+            degree_max: degree.iter().copied().max().unwrap_or(0),
+            degree,
+            neighbours: if param.triad > 0.0 {
+                initial.adjacency_list.clone()
+            } else {
+                Vec::new()
+            },
+            next: 0,
+        }
+    }
+    /// Élément `k` de la liste des sommets répétés.
+    fn repeated(&self, k: usize) -> usize {
+        if k < self.stubs.len() {
+            return self.stubs[k];
+        }
+        let e = (k - self.stubs.len()) / 2;
+        if (k - self.stubs.len()) % 2 == 1 {
+            self.targets[e]
+        } else {
+            self.sources[e]
+        }
+    }
+    /// Choisit les cibles du sommet `self.i` et les ajoute à la fin de `targets`, le sommet
+    /// lui-même à la fin de `sources`.
+    fn attach(&mut self) {
+        let i = self.i;
+        let begin = self.targets.len();
+        // Taille de la liste des sommets répétés, sans les arêtes du sommet en cours d'ajout.
+        let len = self.stubs.len() + 2 * begin;
+        let candidates = if len == 0 { i } else { self.present };
+        let m = if candidates == 0 {
+            0
+        } else if self.param.distinct {
+            self.param.m.min(candidates)
+        } else {
+            self.param.m
+        };
+
+        // Dernier sommet choisi par attachement préférentiel, pour la formation de triades.
+        let mut last: Option<usize> = None;
+        while self.targets.len() - begin < m {
+            // Un sommet sans voisin ne forme pas de triade: attachement préférentiel.
+            let triad = self.param.triad > 0.0;
+            if let Some(l) = last.filter(|&l| triad && !self.neighbours[l].is_empty()) {
+                if self.r.gen::<f64>() < self.param.triad {
+                    let children = &self.neighbours[l];
+                    let w = children[below(self.r, children.len())];
+                    if !self.param.distinct || !self.targets[begin..].contains(&w) {
+                        self.targets.push(w);
+                        continue;
+                    }
+                }
+            }
+
+            let j = if len == 0 {
+                below(self.r, i)
+            } else {
+                let k = below(self.r, len);
+                self.repeated(k)
+            };
+            if self.param.alpha != 1.0 && len > 0 {
+                let k = self.degree[j] as f64;
+                let accept = if self.param.alpha < 1.0 {
+                    k.powf(self.param.alpha - 1.0)
+                } else {
+                    (k / self.degree_max as f64).powf(self.param.alpha - 1.0)
+                };
+                if self.r.gen::<f64>() >= accept {
+                    continue;
+                }
+            }
+            if self.param.distinct && self.targets[begin..].contains(&j) {
+                continue;
+            }
+            self.targets.push(j);
+            last = Some(j);
+        }
+
+        self.sources.resize(self.targets.len(), i);
+        self.degree.push(0);
+        if self.param.triad > 0.0 {
+            self.neighbours.resize_with(i + 1, Vec::new);
+        }
+        for t in begin..self.targets.len() {
+            let j = self.targets[t];
+            if self.degree[j] == 0 {
+                self.present += 1;
+            }
+            self.degree[j] += 1;
+            self.degree[i] += 1;
+            self.degree_max = self.degree_max.max(self.degree[j]);
+            if self.param.triad > 0.0 {
+                self.neighbours[j].push(i);
+                self.neighbours[i].push(j);
+            }
+        }
+        if m > 0 {
+            self.present += 1;
+            self.degree_max = self.degree_max.max(m);
+        }
+        self.i += 1;
+    }
+}
+
+impl<'a, R: Rng + ?Sized> Iterator for PreferentialEdges<'a, R> {
+    type Item = (usize, usize);
+    fn next(&mut self) -> Option<(usize, usize)> {
+        while self.next == self.targets.len() {
+            if self.i >= self.size {
+                return None;
+            }
+            self.attach();
+        }
+        let e = self.next;
+        self.next += 1;
+        Some((self.targets[e], self.sources[e]))
+    }
+}
+
+#[test]
+fn test_triangular() {
+    let pairs: Vec<(usize, usize)> = (0..6).map(triangular).collect();
+    assert_eq!(vec![(0, 1), (0, 2), (1, 2), (0, 3), (1, 3), (2, 3)], pairs);
+}
+#[test]
+fn gen_seed() {
+    let a = Graph::with_seed(7, |r| Graph::gen_barabasi_albert(6, r));
+    assert_eq!(Some(7), a.seed());
+    assert_eq!(
+        vec![(3, 0), (3, 0), (4, 0), (4, 3), (5, 3), (5, 0)],
+        a.edge_list()
+            .filter(|(a, b)| a > b && *a > 2)
+            .collect::<Vec<_>>()
+    );
+
+    let mut r = rng(7);
+    let b = Graph::gen_gilbert(20, &mut r);
+    let c = Graph::gen_gilbert(20, &mut rng(7));
+    assert!(b.edge_list().eq(c.edge_list()));
+    assert_eq!(None, b.seed());
+}
+#[test]
+fn gen_preferential() {
+    let mut initial = Graph::new(Some(5));
+    initial.add((0, 1));
+    initial.add((3, 4));
+    let param = Preferential {
+        m: 3,
+        distinct: true,
+        triad: 0.8,
+        alpha: 1.5,
+    };
+    let g = Graph::gen_preferential(Some(initial), 200, &param, &mut rng(2));
+
+    assert_eq!(200, g.len());
+    assert_eq!(2 + 195 * 3, g.edges());
+    assert!(g.adjacency_list[2].is_empty());
+    for children in g.adjacency_list.iter() {
+        let mut c = children.clone();
+        c.sort();
+        c.dedup();
+        assert_eq!(children.len(), c.len());
+    }
+}
+#[test]
+fn gen_preferential_edgeless() {
+    // Sans arête initiale, les premières cibles n'ont aucun voisin pour former une triade.
+    let param = Preferential {
+        m: 2,
+        triad: 0.9,
+        ..Preferential::default()
+    };
+    let g = Graph::gen_preferential(Some(Graph::new(Some(3))), 50, &param, &mut rng(0));
+    assert_eq!(50, g.len());
+    assert_eq!(47 * 2, g.edges());
+}
+#[test]
+fn gen_small_world() {
+    let ring = Graph::gen_watts_strogatz(20, 4, 0.0, &mut rng(0));
+    assert!(ring.adjacency_list.iter().all(|c| c.len() == 4));
+    assert_eq!(5, ring.distance());
+
+    let g = Graph::gen_watts_strogatz(100, 6, 0.3, &mut rng(0));
+    assert_eq!(300, g.edges());
+    for (n, children) in g.adjacency_list.iter().enumerate() {
+        let mut c = children.clone();
+        c.sort();
+        c.dedup();
+        assert_eq!(children.len(), c.len());
+        assert!(!c.contains(&n));
+    }
+
+    let g = Graph::gen_newman_watts(100, 6, 0.3, &mut rng(0));
+    assert!(g.edges() > 300);
+    assert!(g.distance() < 17);
+}
+#[test]
+fn stream_equals_gen() {
+    fn same<I: Iterator<Item = (usize, usize)>>(g: Graph, edges: I) {
+        let streamed = Graph::new_iter(edges, Some(g.len()));
+        assert_eq!(g.adjacency_list, streamed.adjacency_list);
+    }
+    let param = Preferential {
+        m: 3,
+        distinct: true,
+        triad: 0.5,
+        alpha: 0.8,
+    };
+    assert!(Graph::gen_gilbert(50, &mut rng(1))
+        .edge_list()
+        .eq(Graph::stream_gilbert(50, &mut rng(1))));
+    same(
+        Graph::gen_preferential(None, 300, &param, &mut rng(1)),
+        Graph::stream_preferential(None, 300, &param, &mut rng(1)),
+    );
+    same(
+        Graph::gen_planted_partition(3, 20, 0.5, 0.1, &mut rng(1)).0,
+        Graph::stream_planted_partition(3, 20, 0.5, 0.1, &mut rng(1)),
+    );
+    same(
+        Graph::gen_chung_lu(&[3.0; 100], &mut rng(1)),
+        Graph::stream_chung_lu(&[3.0; 100], &mut rng(1)),
+    );
+    let (g, points) = Graph::gen_geometric(200, 0.1, true, &mut rng(1));
+    same(g, Graph::stream_geometric(&points, 0.1, true));
+    same(
+        Graph::gen_rmat(8, 1000, [0.57, 0.19, 0.19, 0.05], &mut rng(1)),
+        Graph::stream_rmat(8, 1000, [0.57, 0.19, 0.19, 0.05], &mut rng(1)),
+    );
+    same(
+        Graph::gen_prufer_tree(100, &mut rng(1)),
+        Graph::stream_prufer_tree(100, &mut rng(1)),
+    );
+    same(
+        Graph::gen_preferential_tree(100, &mut rng(1)),
+        Graph::stream_preferential_tree(100, &mut rng(1)),
+    );
+    let degrees = [3, 2, 2, 2, 1, 1, 1];
+    let mode = super::Configuration::Erased;
+    same(
+        Graph::gen_configuration(&degrees, mode, &mut rng(1)).unwrap(),
+        Graph::stream_configuration(&degrees, mode, &mut rng(1)).unwrap(),
+    );
+    same(
+        Graph::havel_hakimi(&degrees).unwrap(),
+        Graph::stream_havel_hakimi(&degrees).unwrap(),
+    );
+    let grid = Graph::grid(&[10, 10]);
+    same(
+        grid.random_spanning_tree(&mut rng(1)),
+        grid.stream_random_spanning_tree(&mut rng(1)),
+    );
+}
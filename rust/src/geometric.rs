@@ -0,0 +1,139 @@
+use super::Graph;
+use rand::Rng;
+
+/// Tire `size` points uniformément dans le carré unité.
+pub fn random_points<R: Rng + ?Sized>(size: usize, r: &mut R) -> Vec<(f64, f64)> {
+    (0..size).map(|_| (r.gen(), r.gen())).collect()
+}
+
+impl Graph {
+    /// Génère un graphe géométrique aléatoire: `size` points sont tirés uniformément dans le carré
+    /// unité, et deux points sont reliés si leur distance est inférieure à `radius`. Si `torus`
+    /// est vrai, les bords opposés du carré sont confondus. Retourne le graphe et la position de
+    /// chaque sommet.
+    pub fn gen_geometric<R: Rng + ?Sized>(
+        size: usize,
+        radius: f64,
+        torus: bool,
+        r: &mut R,
+    ) -> (Graph, Vec<(f64, f64)>) {
+        let points = random_points(size, r);
+        let g = Graph::new_iter(Graph::stream_geometric(&points, radius, torus), Some(size));
+        (g, points)
+    }
+    /// Arêtes du graphe géométrique des points `points`, voir `gen_geometric` et `random_points`.
+    /// Les points sont rangés dans des cellules de côté au moins `radius`, seules les cellules
+    /// voisines sont donc comparées. Complexité moyenne: O(S+A), mémoire utilisée: O(S).
+    pub fn stream_geometric(
+        points: &[(f64, f64)],
+        radius: f64,
+        torus: bool,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let size = points.len();
+        // Nombre de cellules par côté, limité pour ne pas avoir plus de cellules que de points.
+        let side = if radius > 0.0 {
+            ((1.0 / radius).floor() as usize).clamp(1, (size as f64).sqrt() as usize + 1)
+        } else {
+            1
+        };
+        let cell = move |x: f64| ((x * side as f64) as usize).min(side - 1);
+        let mut cells: Vec<Vec<usize>> = vec![Vec::new(); side * side];
+        for (n, &(x, y)) in points.iter().enumerate() {
+            cells[cell(x) + side * cell(y)].push(n);
+        }
+
+        let delta = move |a: f64, b: f64| {
+            let d = (a - b).abs();
+            if torus {
+                d.min(1.0 - d)
+            } else {
+                d
+            }
+        };
+        let close = move |a: (f64, f64), b: (f64, f64)| {
+            let (dx, dy) = (delta(a.0, b.0), delta(a.1, b.1));
+            dx * dx + dy * dy < radius * radius
+        };
+
+        // Cellules voisines de celle du sommet n.
+        let neighbourhood = move |n: usize, around: &mut Vec<usize>| {
+            let (cx, cy) = (cell(points[n].0), cell(points[n].1));
+            let s = side as isize;
+            around.clear();
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let (x, y) = (cx as isize + dx, cy as isize + dy);
+                    if !torus && (x < 0 || y < 0 || x >= s || y >= s) {
+                        continue;
+                    }
+                    around.push((x.rem_euclid(s) + s * y.rem_euclid(s)) as usize);
+                }
+            }
+            around.sort_unstable();
+            around.dedup();
+        };
+
+        // Sommet courant, la cellule voisine et le sommet de cette cellule testés.
+        let (mut n, mut c, mut m) = (0, 0, 0);
+        let mut around: Vec<usize> = Vec::with_capacity(9);
+        if size > 0 && radius > 0.0 {
+            neighbourhood(0, &mut around);
+        } else {
+            n = size;
+        }
+        std::iter::from_fn(move || loop {
+            if c >= around.len() {
+                n += 1;
+                if n >= size {
+                    return None;
+                }
+                neighbourhood(n, &mut around);
+                c = 0;
+                m = 0;
+            }
+            let members = &cells[around[c]];
+            if m >= members.len() {
+                c += 1;
+                m = 0;
+                continue;
+            }
+            let other = members[m];
+            m += 1;
+            if other > n && close(points[n], points[other]) {
+                return Some((n, other));
+            }
+        })
+    }
+}
+#[test]
+fn gen_geometric() {
+    use super::rng;
+    let (g, points) = Graph::gen_geometric(500, 0.1, false, &mut rng(3));
+    let mut edges = 0;
+    for a in 0..500 {
+        for b in 0..a {
+            let (dx, dy) = (points[a].0 - points[b].0, points[a].1 - points[b].1);
+            if dx * dx + dy * dy < 0.01 {
+                edges += 1;
+            }
+        }
+    }
+    assert_eq!(edges, g.edges());
+
+    let (g, points) = Graph::gen_geometric(500, 0.1, true, &mut rng(3));
+    let mut edges = 0;
+    for a in 0..500 {
+        for b in 0..a {
+            let dx = (points[a].0 - points[b].0).abs();
+            let dy = (points[a].1 - points[b].1).abs();
+            let (dx, dy) = (dx.min(1.0 - dx), dy.min(1.0 - dy));
+            if dx * dx + dy * dy < 0.01 {
+                edges += 1;
+            }
+        }
+    }
+    assert_eq!(edges, g.edges());
+
+    let (g, _) = Graph::gen_geometric(50, 2.0, false, &mut rng(3));
+    assert_eq!(50 * 49 / 2, g.edges());
+}
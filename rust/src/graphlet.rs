@@ -0,0 +1,278 @@
+use super::{intersection, Graph};
+
+/// Nombre de graphlets (sous-graphes induits connexes) de 3 et 4 sommets, calculé par
+/// `Graph::graphlet_counts`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GraphletCounts {
+    /// Chemins induits de 3 sommets, dont les extrémités ne sont pas reliées: contrairement aux
+    /// triplets connexes de `Triangles::transitivity`, ceux fermés en triangle sont exclus.
+    pub wedges: usize,
+    /// Triangles, comptés comme `Triangles::triangles`.
+    pub triangles: usize,
+    /// Chemins de 4 sommets.
+    pub paths: usize,
+    /// Étoiles à 3 branches.
+    pub stars: usize,
+    /// Cycles de 4 sommets sans corde.
+    pub cycles: usize,
+    /// Triangles avec une arête pendante.
+    pub tailed_triangles: usize,
+    /// Cycles de 4 sommets avec une corde.
+    pub diamonds: usize,
+    /// Cliques de 4 sommets.
+    pub cliques: usize,
+}
+
+/// Vecteur de degrés de graphlets d'un sommet: le nombre de fois où il occupe chacune des 15
+/// orbites des graphlets de 2 à 4 sommets, dans la numérotation de Pržulj:
+///
+/// - 0: arête;
+/// - 1, 2: extrémité et centre d'un chemin de 3 sommets; 3: triangle;
+/// - 4, 5: extrémité et intérieur d'un chemin de 4 sommets;
+/// - 6, 7: feuille et centre d'une étoile;
+/// - 8: cycle de 4 sommets;
+/// - 9, 10, 11: bout de la queue, sommet de degré 2 et sommet de degré 3 d'un triangle à queue;
+/// - 12, 13: sommet de degré 2 et de degré 3 d'un diamant;
+/// - 14: clique de 4 sommets.
+pub type Orbits = [usize; 15];
+
+fn choose2(n: usize) -> usize {
+    n * n.saturating_sub(1) / 2
+}
+
+fn choose3(n: usize) -> usize {
+    n * n.saturating_sub(1) * n.saturating_sub(2) / 6
+}
+
+impl Graph {
+    /// Vecteur de degrés de graphlets de chaque sommet, voir `Orbits`. Seules les cliques de 4
+    /// sommets sont énumérées; les autres orbites sont déduites de nombres de sous-graphes
+    /// (pas forcément induits) obtenus par des formules combinatoires sur les degrés, les
+    /// triangles de chaque arête et les voisins communs, puis corrigées des graphlets plus
+    /// denses qui les contiennent. Les boucles et les arêtes multiples sont ignorées, un arc
+    /// présent dans un seul sens compte comme une arête.
+    /// Complexité: O(somme des carrés des degrés).
+    pub fn graphlets(&self) -> Vec<Orbits> {
+        let adjacency = self.simple_adjacency();
+        let n = self.len();
+        let degree: Vec<usize> = adjacency.iter().map(|c| c.len()).collect();
+        // tri[v][i] = nombre de triangles contenant l'arête de v vers adjacency[v][i].
+        let tri: Vec<Vec<usize>> = adjacency
+            .iter()
+            .map(|c| {
+                c.iter()
+                    .map(|&u| intersection(c, &adjacency[u]).count())
+                    .collect()
+            })
+            .collect();
+        let edge_tri = |a: usize, b: usize| {
+            let i = adjacency[a].binary_search(&b).expect("the edge exists");
+            tri[a][i]
+        };
+        let triangles: Vec<usize> = tri.iter().map(|t| t.iter().sum::<usize>() / 2).collect();
+        // Somme sur les voisins de (degré - 1).
+        let around: Vec<usize> = adjacency
+            .iter()
+            .map(|c| c.iter().map(|&u| degree[u] - 1).sum())
+            .collect();
+
+        // Cliques de 4 sommets, chacune trouvée une fois en orientant les arêtes par degré.
+        let mut k4 = vec![0; n];
+        let rank = |u: usize| (degree[u], u);
+        let out: Vec<Vec<usize>> = adjacency
+            .iter()
+            .enumerate()
+            .map(|(u, c)| c.iter().copied().filter(|&v| rank(v) > rank(u)).collect())
+            .collect();
+        for u in 0..n {
+            for &v in out[u].iter() {
+                let w: Vec<usize> = intersection(&out[u], &out[v]).collect();
+                for &x in w.iter() {
+                    for y in intersection(&w, &out[x]) {
+                        k4[u] += 1;
+                        k4[v] += 1;
+                        k4[x] += 1;
+                        k4[y] += 1;
+                    }
+                }
+            }
+        }
+
+        let mut two_hop = vec![0; n];
+        let mut touched: Vec<usize> = Vec::new();
+        (0..n)
+            .map(|v| {
+                let (d, t, k) = (degree[v], triangles[v], k4[v]);
+                let c = &adjacency[v];
+
+                // Cycles de 4 sommets: paires de voisins ayant un autre voisin commun b.
+                for &u in c.iter() {
+                    for &b in adjacency[u].iter().filter(|&&b| b != v) {
+                        if two_hop[b] == 0 {
+                            touched.push(b);
+                        }
+                        two_hop[b] += 1;
+                    }
+                }
+                let cycles: usize = touched.iter().map(|&b| choose2(two_hop[b])).sum();
+                touched.iter().for_each(|&b| two_hop[b] = 0);
+                touched.clear();
+
+                // Sommes sur les triangles (v, a, b).
+                let (mut diamond_side, mut tailed_side) = (0, 0);
+                for &a in c.iter() {
+                    for b in intersection(c, &adjacency[a]).filter(|&b| b > a) {
+                        diamond_side += edge_tri(a, b) - 1;
+                        tailed_side += degree[a] - 2 + degree[b] - 2;
+                    }
+                }
+
+                let mut o = [0; 15];
+                o[0] = d;
+                o[1] = around[v] - 2 * t;
+                o[2] = choose2(d) - t;
+                o[3] = t;
+                o[14] = k;
+                o[13] = tri[v].iter().map(|&x| choose2(x)).sum::<usize>() - 3 * k;
+                o[12] = diamond_side - 3 * k;
+                o[8] = cycles - o[12] - o[13] - 3 * k;
+                o[11] = t * d.saturating_sub(2) - 2 * o[13] - 3 * k;
+                o[10] = tailed_side - 2 * o[13] - 2 * o[12] - 6 * k;
+                o[9] = c
+                    .iter()
+                    .zip(tri[v].iter())
+                    .map(|(&u, &x)| triangles[u] - x)
+                    .sum::<usize>()
+                    - 2 * o[12]
+                    - 3 * k;
+                o[7] = choose3(d) - o[11] - o[13] - k;
+                o[6] = c.iter().map(|&u| choose2(degree[u] - 1)).sum::<usize>()
+                    - o[9]
+                    - o[10]
+                    - 2 * o[12]
+                    - o[13]
+                    - 3 * k;
+                o[5] = (d.saturating_sub(1)) * around[v]
+                    - 2 * t
+                    - 2 * o[8]
+                    - o[10]
+                    - 2 * o[11]
+                    - 2 * o[12]
+                    - 4 * o[13]
+                    - 6 * k;
+                o[4] = c.iter().map(|&u| around[u] - (d - 1)).sum::<usize>()
+                    - 2 * t
+                    - 2 * o[8]
+                    - 2 * o[9]
+                    - o[10]
+                    - 4 * o[12]
+                    - 2 * o[13]
+                    - 6 * k;
+                o
+            })
+            .collect()
+    }
+    /// Nombre de graphlets de 3 et 4 sommets de chaque type, déduit des orbites de
+    /// `graphlets`.
+    /// ```
+    /// let g = graph::Graph::complete(5).graphlet_counts();
+    /// assert_eq!(10, g.triangles);
+    /// assert_eq!(5, g.cliques);
+    /// assert_eq!(0, g.paths);
+    /// ```
+    pub fn graphlet_counts(&self) -> GraphletCounts {
+        let mut sum = [0; 15];
+        for o in self.graphlets().iter() {
+            sum.iter_mut().zip(o.iter()).for_each(|(s, x)| *s += x);
+        }
+        GraphletCounts {
+            wedges: sum[2],
+            triangles: sum[3] / 3,
+            paths: sum[4] / 2,
+            stars: sum[7],
+            cycles: sum[8] / 4,
+            tailed_triangles: sum[11],
+            diamonds: sum[13] / 2,
+            cliques: sum[14] / 4,
+        }
+    }
+}
+#[test]
+fn test_graphlets() {
+    // Comparaison avec l'énumération de tous les ensembles de 3 et 4 sommets, aussi sur un
+    // graphe dont des arcs n'existent que dans un sens.
+    let geometric = Graph::gen_geometric(40, 0.25, false, &mut super::rng(7)).0;
+    let directed = Graph::gen_gilbert(12, &mut super::rng(1));
+    for g in [&geometric, &directed].iter() {
+        let n = g.len();
+        let adjacency = g.simple_adjacency();
+        let linked = |a: usize, b: usize| adjacency[a].binary_search(&b).is_ok();
+        let mut expected = vec![[0; 15]; n];
+        for v in 0..n {
+            expected[v][0] = adjacency[v].len();
+        }
+        for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    let nodes = [a, b, c];
+                    let deg = |x: usize| nodes.iter().filter(|&&y| linked(x, y)).count();
+                    let edges: usize = nodes.iter().map(|&x| deg(x)).sum::<usize>() / 2;
+                    for &x in nodes.iter() {
+                        match (edges, deg(x)) {
+                            (2, 1) => expected[x][1] += 1,
+                            (2, 2) => expected[x][2] += 1,
+                            (3, _) => expected[x][3] += 1,
+                            _ => {}
+                        }
+                    }
+                    for d in c + 1..n {
+                        let nodes = [a, b, c, d];
+                        let deg = |x: usize| nodes.iter().filter(|&&y| linked(x, y)).count();
+                        let degrees: Vec<usize> = nodes.iter().map(|&x| deg(x)).collect();
+                        let edges = degrees.iter().sum::<usize>() / 2;
+                        let star = degrees.contains(&3);
+                        for (&x, &dx) in nodes.iter().zip(degrees.iter()) {
+                            let orbit = match (edges, star, dx) {
+                                (3, false, 1) if !degrees.contains(&0) => 4,
+                                (3, false, 2) if !degrees.contains(&0) => 5,
+                                (3, true, 1) => 6,
+                                (3, true, 3) => 7,
+                                (4, false, _) if !degrees.contains(&0) && !degrees.contains(&1) => {
+                                    8
+                                }
+                                (4, true, 1) => 9,
+                                (4, true, 2) => 10,
+                                (4, true, 3) => 11,
+                                (5, _, 2) => 12,
+                                (5, _, 3) => 13,
+                                (6, _, _) => 14,
+                                _ => continue,
+                            };
+                            expected[x][orbit] += 1;
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(expected, g.graphlets());
+        assert_eq!(g.clustering().triangles, g.graphlet_counts().triangles);
+    }
+    let g = geometric;
+
+    let c = g.graphlet_counts();
+    assert_eq!(g.clustering().triangles, c.triangles);
+    assert_eq!(
+        GraphletCounts {
+            wedges: 4,
+            cycles: 1,
+            ..GraphletCounts::default()
+        },
+        Graph::cycle(4).graphlet_counts()
+    );
+
+    let s = g.stats_with(&super::StatsOptions {
+        graphlets: true,
+        ..super::StatsOptions::default()
+    });
+    assert_eq!(Some(c), s.graphlets);
+}
@@ -0,0 +1,308 @@
+use super::Graph;
+
+/// Mode de correspondance de `Graph::matches`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Matching {
+    /// Bijection qui conserve les arêtes et les non-arêtes: les deux graphes sont isomorphes.
+    Isomorphism,
+    /// Chaque arête du motif correspond à une arête du graphe (monomorphisme).
+    Subgraph,
+    /// Comme `Subgraph`, et deux sommets non reliés du motif correspondent à deux sommets non
+    /// reliés du graphe.
+    InducedSubgraph,
+}
+
+/// Itérateur des correspondances du motif dans le graphe, voir `Graph::matches`.
+pub struct Mappings {
+    mode: Matching,
+    pattern: Vec<Vec<usize>>,
+    target: Vec<Vec<usize>>,
+    /// Ordre dans lequel les sommets du motif sont placés.
+    order: Vec<usize>,
+    /// Voisin du sommet du motif placé avant lui, qui restreint ses candidats.
+    parent: Vec<Option<usize>>,
+    mapping: Vec<usize>,
+    inverse: Vec<usize>,
+    stack: Vec<(Vec<usize>, usize)>,
+    started: bool,
+}
+
+const UNSET: usize = usize::MAX;
+
+impl Mappings {
+    fn new(mode: Matching, pattern: Vec<Vec<usize>>, target: Vec<Vec<usize>>) -> Mappings {
+        let n = pattern.len();
+        // Ordre de VF2++: parcours en largeur depuis le sommet de plus grand degré de chaque
+        // composante, chaque niveau étant trié par degré décroissant.
+        let mut order = Vec::with_capacity(n);
+        let mut rank = vec![UNSET; n];
+        let mut by_degree: Vec<usize> = (0..n).collect();
+        by_degree.sort_by_key(|&u| (std::cmp::Reverse(pattern[u].len()), u));
+        for &root in by_degree.iter() {
+            if rank[root] != UNSET {
+                continue;
+            }
+            rank[root] = order.len();
+            order.push(root);
+            let mut level = vec![root];
+            while !level.is_empty() {
+                let mut next: Vec<usize> = Vec::new();
+                for &u in level.iter() {
+                    for &v in pattern[u].iter() {
+                        if rank[v] == UNSET {
+                            rank[v] = 0;
+                            next.push(v);
+                        }
+                    }
+                }
+                next.sort_by_key(|&u| (std::cmp::Reverse(pattern[u].len()), u));
+                for &u in next.iter() {
+                    rank[u] = order.len();
+                    order.push(u);
+                }
+                level = next;
+            }
+        }
+        let parent = (0..n)
+            .map(|u| pattern[u].iter().copied().find(|&v| rank[v] < rank[u]))
+            .collect();
+
+        let m = target.len();
+        Mappings {
+            mode,
+            pattern,
+            target,
+            order,
+            parent,
+            mapping: vec![UNSET; n],
+            inverse: vec![UNSET; m],
+            stack: Vec::new(),
+            started: false,
+        }
+    }
+    /// Candidats du sommet `u` du motif: les voisins de l'image de son parent, sinon tous.
+    fn candidates(&self, u: usize) -> Vec<usize> {
+        match self.parent[u] {
+            Some(p) => self.target[self.mapping[p]].clone(),
+            None => (0..self.target.len()).collect(),
+        }
+    }
+    /// Le sommet `u` du motif peut-il être placé sur le sommet `t` du graphe?
+    fn feasible(&self, u: usize, t: usize) -> bool {
+        if self.inverse[t] != UNSET {
+            return false;
+        }
+        let (du, dt) = (self.pattern[u].len(), self.target[t].len());
+        if dt < du || (self.mode == Matching::Isomorphism && dt != du) {
+            return false;
+        }
+        // Les voisins déjà placés de u doivent être voisins de t.
+        let mut adjacent = 0;
+        for &v in self.pattern[u].iter() {
+            let image = self.mapping[v];
+            if image != UNSET {
+                if self.target[image].binary_search(&t).is_err() {
+                    return false;
+                }
+                adjacent += 1;
+            }
+        }
+        if self.mode == Matching::Subgraph {
+            return true;
+        }
+        // Et t ne doit pas avoir d'autre voisin déjà utilisé.
+        let used = self.target[t]
+            .iter()
+            .filter(|&&w| self.inverse[w] != UNSET)
+            .count();
+        used == adjacent
+    }
+}
+
+impl Iterator for Mappings {
+    /// Image dans le graphe de chaque sommet du motif.
+    type Item = Vec<usize>;
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if !self.started {
+            self.started = true;
+            if self.order.is_empty() {
+                return Some(Vec::new());
+            }
+            let invalid = self.mode == Matching::Isomorphism
+                && (self.pattern.len() != self.target.len()
+                    || self.pattern.iter().map(|c| c.len()).sum::<usize>()
+                        != self.target.iter().map(|c| c.len()).sum::<usize>());
+            if invalid || self.pattern.len() > self.target.len() {
+                return None;
+            }
+            let first = self.candidates(self.order[0]);
+            self.stack.push((first, 0));
+        }
+
+        loop {
+            let depth = self.stack.len().checked_sub(1)?;
+            let u = self.order[depth];
+            // Défait le choix précédent à cette profondeur.
+            if self.mapping[u] != UNSET {
+                self.inverse[self.mapping[u]] = UNSET;
+                self.mapping[u] = UNSET;
+            }
+            let (candidates, next) = self.stack.last_mut()?;
+            let t = match candidates.get(*next) {
+                Some(&t) => t,
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+            *next += 1;
+            if !self.feasible(u, t) {
+                continue;
+            }
+            self.mapping[u] = t;
+            self.inverse[t] = u;
+            if depth + 1 == self.order.len() {
+                return Some(self.mapping.clone());
+            }
+            let candidates = self.candidates(self.order[depth + 1]);
+            self.stack.push((candidates, 0));
+        }
+    }
+}
+
+/// Mélange `x` dans le hachage `h` (fonction de finalisation de SplitMix64), le résultat ne
+/// dépend pas de la plateforme.
+fn mix(h: u64, x: u64) -> u64 {
+    let mut z = h ^ x.wrapping_add(0x9e37_79b9_7f4a_7c15).wrapping_add(h << 6);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+impl Graph {
+    /// Correspondances du motif `pattern` dans le graphe avec un algorithme de type VF2++: les
+    /// sommets du motif sont placés un par un dans l'ordre d'un parcours en largeur depuis les
+    /// sommets de plus grand degré, chacun parmi les voisins de l'image d'un voisin déjà placé,
+    /// et les placements incompatibles sont abandonnés. Chaque correspondance donne l'image de
+    /// chaque sommet du motif; les automorphismes du motif donnent des correspondances
+    /// différentes. Les boucles et les arêtes multiples sont ignorées.
+    /// ```
+    /// use graph::{Graph, Matching};
+    /// let triangles = Graph::complete(4).matches(&Graph::cycle(3), Matching::Subgraph);
+    /// assert_eq!(4 * 6, triangles.count());
+    /// ```
+    pub fn matches(&self, pattern: &Graph, mode: Matching) -> Mappings {
+        Mappings::new(mode, pattern.simple_adjacency(), self.simple_adjacency())
+    }
+    /// Teste si les deux graphes sont isomorphes, voir `matches`.
+    pub fn is_isomorphic(&self, other: &Graph) -> bool {
+        let degrees = |g: &Graph| {
+            let mut d: Vec<usize> = g.simple_adjacency().iter().map(|c| c.len()).collect();
+            d.sort_unstable();
+            d
+        };
+        degrees(self) == degrees(other)
+            && self.matches(other, Matching::Isomorphism).next().is_some()
+    }
+    /// Hachage de Weisfeiler-Lehman: chaque sommet reçoit une étiquette initiale égale à son
+    /// degré, puis à chaque itération le hachage de son étiquette et de celles de ses voisins.
+    /// Le résultat combine toutes les étiquettes, indépendamment de la numérotation des sommets.
+    /// Deux graphes isomorphes ont le même hachage; deux hachages différents prouvent donc que
+    /// les graphes ne sont pas isomorphes. Les boucles et les arêtes multiples sont ignorées.
+    /// Complexité: O(iterations * (S + A log A)).
+    pub fn wl_hash(&self, iterations: usize) -> u64 {
+        let adjacency = self.simple_adjacency();
+        let mut labels: Vec<u64> = adjacency.iter().map(|c| c.len() as u64).collect();
+        let mut all: Vec<u64> = labels.clone();
+        let mut neighbours: Vec<u64> = Vec::new();
+        for _ in 0..iterations {
+            labels = adjacency
+                .iter()
+                .enumerate()
+                .map(|(u, c)| {
+                    neighbours.clear();
+                    neighbours.extend(c.iter().map(|&v| labels[v]));
+                    neighbours.sort_unstable();
+                    neighbours.iter().fold(mix(0, labels[u]), |h, &l| mix(h, l))
+                })
+                .collect();
+            all.extend(labels.iter().copied());
+        }
+        all.sort_unstable();
+        all.iter()
+            .fold(mix(0, self.len() as u64), |h, &l| mix(h, l))
+    }
+}
+#[test]
+fn test_isomorphism() {
+    // Petersen renuméroté.
+    let permutation = [3, 7, 0, 9, 1, 5, 2, 8, 6, 4];
+    let g = Graph::petersen();
+    let relabelled = Graph::new_iter(
+        g.edge_list()
+            .filter(|(a, b)| a < b)
+            .map(|(a, b)| (permutation[a], permutation[b])),
+        Some(10),
+    );
+    assert!(g.is_isomorphic(&relabelled));
+    let mapping = relabelled
+        .matches(&g, Matching::Isomorphism)
+        .next()
+        .unwrap();
+    assert!(g
+        .edge_list()
+        .all(|(a, b)| relabelled.adjacency_list[mapping[a]].contains(&mapping[b])));
+    // 120 automorphismes.
+    assert_eq!(120, g.matches(&g, Matching::Isomorphism).count());
+
+    // Le prisme pentagonal est aussi 3-régulier à 10 sommets, mais a des 4-cycles.
+    let mut prism = Graph::cycle(5);
+    for i in 0..5 {
+        prism.push((5 + i, 5 + (i + 1) % 5));
+        prism.push((i, 5 + i));
+    }
+    assert!(!g.is_isomorphic(&prism));
+    assert_eq!(g.wl_hash(3), prism.wl_hash(3));
+    assert_eq!(g.wl_hash(3), relabelled.wl_hash(3));
+    assert_ne!(Graph::path(4).wl_hash(2), Graph::star(4).wl_hash(2));
+    assert_eq!(0, g.matches(&Graph::cycle(4), Matching::Subgraph).count());
+    assert_eq!(
+        5 * 2 * 4,
+        prism.matches(&Graph::cycle(4), Matching::Subgraph).count()
+    );
+}
+#[test]
+fn test_subgraph_matching() {
+    let p3 = Graph::path(3);
+    assert_eq!(8, Graph::cycle(4).matches(&p3, Matching::Subgraph).count());
+    assert_eq!(
+        8,
+        Graph::cycle(4)
+            .matches(&p3, Matching::InducedSubgraph)
+            .count()
+    );
+    assert_eq!(
+        24,
+        Graph::complete(4).matches(&p3, Matching::Subgraph).count()
+    );
+    assert_eq!(
+        0,
+        Graph::complete(4)
+            .matches(&p3, Matching::InducedSubgraph)
+            .count()
+    );
+
+    // Motif non connexe: deux sommets isolés.
+    let pair = Graph::new(Some(2));
+    assert_eq!(
+        4 * 3,
+        Graph::path(4).matches(&pair, Matching::Subgraph).count()
+    );
+    assert_eq!(
+        3 * 2,
+        Graph::path(4)
+            .matches(&pair, Matching::InducedSubgraph)
+            .count()
+    );
+    assert_eq!(0, p3.matches(&Graph::path(4), Matching::Subgraph).count());
+}
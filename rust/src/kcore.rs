@@ -0,0 +1,145 @@
+use super::Graph;
+
+/// Décomposition en k-cœurs, calculée par `Graph::cores`.
+#[derive(Debug, Clone)]
+pub struct Cores {
+    /// Nombre de cœur de chaque sommet: le plus grand k tel que le sommet appartienne au k-cœur.
+    pub core: Vec<usize>,
+    /// Ordre de dégénérescence: chaque sommet a au plus `degeneracy()` voisins placés après lui.
+    pub order: Vec<usize>,
+}
+
+impl Cores {
+    /// Dégénérescence du graphe: le plus grand nombre de cœur.
+    pub fn degeneracy(&self) -> usize {
+        self.core.iter().copied().max().unwrap_or(0)
+    }
+    /// Nombre de sommets pour chaque nombre de cœur. Longueur = degeneracy+1
+    pub fn distrib(&self) -> Vec<usize> {
+        let mut distrib = vec![0; self.degeneracy() + 1];
+        self.core.iter().for_each(|&c| distrib[c] += 1);
+        distrib
+    }
+}
+
+impl Graph {
+    /// Décomposition en k-cœurs avec l'algorithme de Batagelj et Zaversnik: les sommets sont
+    /// rangés par degré avec un tri par paquets, puis retirés un par un en diminuant le degré de
+    /// leurs voisins. Les boucles et les arêtes multiples sont ignorées. Complexité: O(S+A).
+    /// ```
+    /// let c = graph::Graph::petersen().cores();
+    /// assert_eq!(3, c.degeneracy());
+    /// ```
+    pub fn cores(&self) -> Cores {
+        let adjacency = self.simple_adjacency();
+        let n = self.len();
+        let mut degree: Vec<usize> = adjacency.iter().map(|c| c.len()).collect();
+        let degree_max = degree.iter().copied().max().unwrap_or(0);
+
+        // bin[d] = position du premier sommet de degré d dans vert.
+        let mut bin = vec![0; degree_max + 1];
+        degree.iter().for_each(|&d| bin[d] += 1);
+        let mut start = 0;
+        for b in bin.iter_mut() {
+            let count = *b;
+            *b = start;
+            start += count;
+        }
+        let mut vert = vec![0; n];
+        let mut pos = vec![0; n];
+        for v in 0..n {
+            pos[v] = bin[degree[v]];
+            vert[pos[v]] = v;
+            bin[degree[v]] += 1;
+        }
+        for d in (1..bin.len()).rev() {
+            bin[d] = bin[d - 1];
+        }
+        if let Some(b) = bin.first_mut() {
+            *b = 0;
+        }
+
+        for i in 0..n {
+            let v = vert[i];
+            for &u in adjacency[v].iter() {
+                if degree[u] > degree[v] {
+                    // Échange u avec le premier sommet de même degré, puis diminue son degré.
+                    let du = degree[u];
+                    let (pu, pw) = (pos[u], bin[du]);
+                    let w = vert[pw];
+                    if u != w {
+                        pos[u] = pw;
+                        vert[pu] = w;
+                        pos[w] = pu;
+                        vert[pw] = u;
+                    }
+                    bin[du] += 1;
+                    degree[u] -= 1;
+                }
+            }
+        }
+
+        Cores {
+            core: degree,
+            order: vert,
+        }
+    }
+    /// Sous-graphe induit par le k-cœur: les sommets de nombre de cœur au moins `k`. Retourne le
+    /// sous-graphe et l'indice d'origine de chacun de ses sommets.
+    pub fn k_core(&self, k: usize) -> (Graph, Vec<usize>) {
+        let keep: Vec<bool> = self.cores().core.iter().map(|&c| c >= k).collect();
+        self.subgraph(&keep)
+    }
+}
+#[test]
+fn test_cores() {
+    // Une 4-clique (0..4), un triangle 4-5-6 relié à la clique, une queue 7-8.
+    let mut g = Graph::complete(4);
+    g.push((3, 4));
+    g.push((4, 5));
+    g.push((5, 6));
+    g.push((6, 4));
+    g.push((6, 7));
+    g.push((7, 8));
+    g.push((7, 8));
+    g.push((9, 9));
+
+    let c = g.cores();
+    assert_eq!(vec![3, 3, 3, 3, 2, 2, 2, 1, 1, 0], c.core);
+    assert_eq!(3, c.degeneracy());
+    assert_eq!(vec![1, 2, 3, 4], c.distrib());
+    let s = g.stats_with(&super::StatsOptions {
+        cores: true,
+        ..super::StatsOptions::default()
+    });
+    assert_eq!(Some(c.distrib()), s.core_distrib);
+
+    // Chaque sommet a au plus degeneracy voisins après lui dans l'ordre.
+    let mut rank = vec![0; g.len()];
+    c.order.iter().enumerate().for_each(|(i, &n)| rank[n] = i);
+    let adjacency = g.simple_adjacency();
+    for n in 0..g.len() {
+        let later = adjacency[n].iter().filter(|&&m| rank[m] > rank[n]).count();
+        assert!(later <= c.degeneracy());
+    }
+
+    let (sub, origin) = g.k_core(2);
+    assert_eq!(vec![0, 1, 2, 3, 4, 5, 6], origin);
+    assert_eq!(6 + 4, sub.edges());
+}
+#[test]
+fn cores_and_mark_tree() {
+    // mark_tree retire exactement les sommets hors du 2-cœur.
+    let g = Graph::gen_preferential(
+        Some(Graph::cycle(5)),
+        500,
+        &super::Preferential {
+            m: 1,
+            ..super::Preferential::default()
+        },
+        &mut super::rng(3),
+    );
+    let (whitelist, _, _) = g.mark_tree();
+    let core: Vec<bool> = g.cores().core.iter().map(|&c| c >= 2).collect();
+    assert_eq!(core, whitelist);
+}
@@ -0,0 +1,97 @@
+use super::Graph;
+use rand::Rng;
+
+impl Graph {
+    /// Génère un graphe R-MAT de `2^scale` sommets et `edges` arêtes: chaque arête est placée dans
+    /// la matrice d'adjacence en choisissant récursivement un de ses quatre quadrants avec les
+    /// probabilités `probs = [a, b, c, d]` (normalisées si leur somme diffère de 1). Les boucles
+    /// sont écartées et les arêtes multiples conservées. Complexité: O(S + A*scale).
+    /// ```
+    /// let g = graph::Graph::gen_rmat(10, 5000, [0.57, 0.19, 0.19, 0.05], &mut graph::rng(0));
+    /// assert_eq!(1024, g.len());
+    /// assert!(g.edges() <= 5000);
+    /// ```
+    pub fn gen_rmat<R: Rng + ?Sized>(
+        scale: usize,
+        edges: usize,
+        probs: [f64; 4],
+        r: &mut R,
+    ) -> Graph {
+        Graph::new_iter(Graph::stream_rmat(scale, edges, probs, r), Some(1 << scale))
+    }
+    /// Arêtes du graphe R-MAT, voir `gen_rmat`. La mémoire utilisée est constante.
+    pub fn stream_rmat<'a, R: Rng + ?Sized>(
+        scale: usize,
+        edges: usize,
+        probs: [f64; 4],
+        r: &'a mut R,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let total: f64 = probs.iter().sum();
+        let (a, b, c) = (probs[0] / total, probs[1] / total, probs[2] / total);
+
+        (0..edges)
+            .map(move |_| {
+                let (mut u, mut v) = (0, 0);
+                for level in (0..scale).rev() {
+                    let x: f64 = r.gen();
+                    let (du, dv) = if x < a {
+                        (0, 0)
+                    } else if x < a + b {
+                        (0, 1)
+                    } else if x < a + b + c {
+                        (1, 0)
+                    } else {
+                        (1, 1)
+                    };
+                    u |= du << level;
+                    v |= dv << level;
+                }
+                (u, v)
+            })
+            .filter(|(u, v)| u != v)
+    }
+    /// Génère un graphe de Kronecker stochastique: la matrice de probabilités est la puissance de
+    /// Kronecker `scale` de la matrice initiatrice `[[a, b], [c, d]]`. Le nombre d'arêtes tirées
+    /// est l'espérance `(a+b+c+d)^scale`, puis chaque arête est placée comme dans `gen_rmat`.
+    pub fn gen_kronecker<R: Rng + ?Sized>(
+        initiator: [[f64; 2]; 2],
+        scale: usize,
+        r: &mut R,
+    ) -> Graph {
+        Graph::new_iter(
+            Graph::stream_kronecker(initiator, scale, r),
+            Some(1 << scale),
+        )
+    }
+    /// Arêtes du graphe de Kronecker stochastique, voir `gen_kronecker`.
+    pub fn stream_kronecker<'a, R: Rng + ?Sized>(
+        initiator: [[f64; 2]; 2],
+        scale: usize,
+        r: &'a mut R,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let probs = [
+            initiator[0][0],
+            initiator[0][1],
+            initiator[1][0],
+            initiator[1][1],
+        ];
+        let edges = probs.iter().sum::<f64>().powi(scale as i32).round() as usize;
+        Graph::stream_rmat(scale, edges, probs, r)
+    }
+}
+#[test]
+fn gen_rmat() {
+    use super::rng;
+    let g = Graph::gen_rmat(8, 2000, [0.25; 4], &mut rng(1));
+    assert_eq!(256, g.len());
+    assert!(g.edges() > 1980);
+
+    // Sans les quadrants b et c, toutes les arêtes sont des boucles.
+    let g = Graph::gen_rmat(8, 2000, [0.5, 0.0, 0.0, 0.5], &mut rng(1));
+    assert_eq!(0, g.edges());
+
+    let g = Graph::gen_kronecker([[0.9, 0.5], [0.5, 0.2]], 10, &mut rng(1));
+    assert_eq!(1024, g.len());
+    let degree_max = g.adjacency_list.iter().map(|c| c.len()).max().unwrap();
+    assert!(degree_max > 10 * g.edges() * 2 / g.len());
+}
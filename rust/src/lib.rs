@@ -1,9 +1,51 @@
+mod assortativity;
+mod betweenness;
+mod biconnected;
+mod block;
+mod centrality;
+mod clique;
+mod clustering;
+mod colouring;
+mod community;
+mod compare;
+mod distance;
+mod family;
+mod gen;
+mod geometric;
+mod graphlet;
+mod isomorphism;
+mod kcore;
+mod kronecker;
 mod parse;
+mod partition;
+mod powerlaw;
 mod printer;
+mod rewire;
+mod sequence;
+mod tree;
+
+pub use betweenness::{top_k, Betweenness};
+pub use biconnected::Biconnected;
+pub use block::Lfr;
+pub use centrality::{eigenvector, katz, pagerank, Centrality, Katz, PageRank, Successors};
+pub use clique::Cliques;
+pub use clustering::{Clustering, Triangles};
+pub use colouring::{Bipartite, Colouring, Projection};
+pub use community::Louvain;
+pub use compare::{adjusted_rand, nmi, variation_of_information, ClusterQuality};
+pub use distance::DistanceDistribution;
+pub use gen::{rng, Preferential, SeedRng};
+pub use geometric::random_points;
+pub use graphlet::{GraphletCounts, Orbits};
+pub use isomorphism::{Mappings, Matching};
+pub use kcore::Cores;
+pub use parse::{load_partition, save_edges, save_metis_partition, save_partition};
+pub use partition::Partitioning;
+pub use powerlaw::PowerLaw;
+pub use rewire::ZScores;
+pub use sequence::{is_graphical, Configuration};
 
-use rand;
 use std::collections::VecDeque;
-use std::fs::File;
 use std::time::{Duration, Instant};
 
 /// Un graphe, il contient la liste où chaque sommet a la liste de tous ses sommets voisins.
@@ -26,6 +68,8 @@ use std::time::{Duration, Instant};
 #[derive(Debug)]
 pub struct Graph {
     adjacency_list: Vec<Vec<usize>>,
+    /// Graine ayant servi à générer le graphe, enregistrée par `save`.
+    seed: Option<u64>,
 }
 
 /// Les statistiques d'un graphe. Généré par `graph.stats()`.
@@ -43,58 +87,49 @@ pub struct Stats {
     pub degree_max: usize,
     /// Distance = plus long plus court chemin.
     pub distance: usize,
+    /// Nombre de triangles, si demandé par `StatsOptions::clustering` en mode exact.
+    pub triangles: Option<usize>,
+    /// Moyenne des coefficients de clustering locaux, si demandé par `StatsOptions::clustering`.
+    pub clustering_average: Option<f64>,
+    /// Coefficient de clustering global (transitivité), si demandé par
+    /// `StatsOptions::clustering`.
+    pub transitivity: Option<f64>,
+    /// Nombre de sommets pour chaque nombre de cœur, si demandé par `StatsOptions::cores`.
+    /// Longueur = dégénérescence+1
+    pub core_distrib: Option<Vec<usize>>,
+    /// Nombre de cliques maximales de chaque taille, si demandé par `StatsOptions::cliques`.
+    /// Longueur = nombre de clique+1
+    pub clique_distrib: Option<Vec<usize>>,
+    /// Nombre de graphlets de 3 et 4 sommets, si demandé par `StatsOptions::graphlets`.
+    pub graphlets: Option<GraphletCounts>,
+    /// Distribution des distances de la plus grande composante connexe, si demandée par
+    /// `StatsOptions::distances`.
+    pub distances: Option<DistanceDistribution>,
     /// Temps pour obtenir ces statistiques.
     pub duration: Duration,
 }
 
-impl Graph {
-    /// Génération de graphe avec le modèle d'Edgar Gilbert.
-    pub fn gen_gilbert(size: usize) -> Graph {
-        use rand::prelude::*;
-        let mut g = Graph::new(Some(size));
-        let mut r = rand::thread_rng();
-
-        for i in 0..size {
-            for j in 0..size {
-                if r.gen::<bool>() {
-                    g.adjacency_list[i].push(j);
-                }
-            }
-        }
-
-        g
-    }
-    /// Génère un graphe de Barabàsi-Albert. Utilise une complexité temporelle et mémoire linéiare.
-    pub fn gen_barabasi_albert(size: usize) -> Graph {
-        use rand::prelude::*;
-
-        let mut g = Graph::new(Some(size));
-        g.add((0, 1));
-        g.add((1, 0));
-        g.add((0, 2));
-        g.add((2, 0));
-        g.add((2, 1));
-        g.add((1, 2));
-
-        let mut p: Vec<usize> = Vec::with_capacity(size * 4);
-        p.extend_from_slice(&[0, 0, 1, 1, 2, 2]);
-
-        let mut r = rand::thread_rng();
-        for i in 3..size {
-            for _ in 0..2 {
-                let j = p[r.gen::<usize>() % p.len()];
-                p.push(i);
-                p.push(j);
-                g.add((j, i));
-            }
-        }
+/// Statistiques optionnelles à calculer avec `graph.stats_with()`.
+#[derive(Debug, Clone, Default)]
+pub struct StatsOptions {
+    /// Calcule le nombre de triangles et les coefficients de clustering.
+    pub clustering: Option<Clustering>,
+    /// Calcule la distribution des nombres de cœur, voir `Graph::cores`.
+    pub cores: bool,
+    /// Calcule la distribution des tailles des cliques maximales, voir `Graph::cliques`.
+    pub cliques: bool,
+    /// Compte les graphlets de 3 et 4 sommets, voir `Graph::graphlet_counts`.
+    pub graphlets: bool,
+    /// Calcule la distribution exacte des distances, voir `Graph::distance_distribution`.
+    pub distances: bool,
+}
 
-        g
-    }
+impl Graph {
     /// Crée un nouveau graphe vide. Pour ajouter des sommets utiliser les méthodes `add` ou `push`.
     pub fn new(size: Option<usize>) -> Graph {
         Graph {
             adjacency_list: vec![vec![0; 0]; size.unwrap_or(0)],
+            seed: None,
         }
     }
     /// Ajoute un nouvel arc si `begin` et `end` sont inférieur à `self.len()`.
@@ -111,7 +146,7 @@ impl Graph {
         use std::cmp::max;
 
         let l = max(self.len(), max(a, b) + 1);
-        self.adjacency_list.resize_with(l, || vec![]);
+        self.adjacency_list.resize_with(l, Vec::new);
 
         self.adjacency_list[a].push(b);
         self.adjacency_list[b].push(a);
@@ -133,14 +168,12 @@ impl Graph {
     /// Charge un graphe à partir du fichier `f` en CSV ou TAB suivant le sont préfixe.
     /// voir les méthodes `load_csv` et `load_tab` pour plus de détails.
     pub fn load(f: &str, size: Option<usize>) -> Result<Graph, String> {
-        match f.strip_suffix(".csv") {
-            Some(..) => return Graph::load_csv(f, size),
-            _ => {}
+        if f.ends_with(".csv") {
+            return Graph::load_csv(f, size);
         }
 
-        match f.strip_suffix(".txt") {
-            Some(..) => return Graph::load_tab(f, size),
-            _ => {}
+        if f.ends_with(".txt") {
+            return Graph::load_tab(f, size);
         }
 
         Err(format!("Unknow extension of the file {:?}", f))
@@ -167,7 +200,7 @@ impl Graph {
                     .to_string()
             })
             .enumerate()
-            .filter(|(_, l)| l.len() > 0)
+            .filter(|(_, l)| !l.is_empty())
             .filter_map(|(num, l)| match parse::tab(&l) {
                 Ok(arc) => Some(arc),
                 Err(e) => {
@@ -178,8 +211,9 @@ impl Graph {
             size,
         ))
     }
-    /// Charge un graphe à partir du fichier pointé par `f`; la première ligne du fichier est ignorée,
-    /// les autres lignes doivent contenir le sommet de départ, une virgule et le sommet d'arrivé.
+    /// Charge un graphe à partir du fichier pointé par `f`; les lignes commençant par un croisillon
+    /// `'#'` et la première ligne restante (l'en-tête) sont ignorées, les autres lignes doivent
+    /// contenir le sommet de départ, une virgule et le sommet d'arrivé.
     /// Si `size` n'est pas défini, le graphe sera agrandi pour contenir tout les sommets, sinon les
     /// sommets trop grands seront ignorés.
     pub fn load_csv(f: &str, size: Option<usize>) -> Result<Graph, String> {
@@ -192,8 +226,9 @@ impl Graph {
             .take_while(|r| r.is_ok())
             .filter_map(|r| r.ok())
             .enumerate()
+            .filter(|(_, l)| !l.starts_with('#'))
             .skip(1)
-            .filter(|(_, l)| l.len() > 0)
+            .filter(|(_, l)| !l.is_empty())
             .filter_map(|(num, l)| match parse::csv(&l) {
                 Ok(arc) => Some(arc),
                 Err(e) => {
@@ -205,32 +240,22 @@ impl Graph {
         ))
     }
     /// Enregistre le graphe dans le fichier `name`; le format est déterminé par les extentions qui
-    /// penvent être ".txt" ou bien ".csv".
+    /// penvent être ".txt" ou bien ".csv". Si le graphe a été généré avec une graine, elle est
+    /// inscrite en commentaire au début du fichier.
     pub fn save(&self, name: &str) -> Result<(), String> {
-        use std::io::Write;
-        let e = |err| format!("Fail to write into {:?} {}", name, err);
-        let mut file = File::create(name).map_err(e)?;
-
-        let writer = if name.strip_suffix(".csv").is_some() {
-            write!(file, "id1,id2\n").map_err(e)?;
-            parse::save_csv
-        } else if name.strip_suffix(".txt").is_some() {
-            write!(file, "# FromNodeId	ToNodeId\n").map_err(e)?;
-            parse::save_txt
-        } else {
-            return Err(format!("Unknow extension of the file {:?}", name));
-        };
-
-        self.edge_list()
-            .filter(|couple| couple.0 < couple.1)
-            .map(|couple| writer(&mut file, couple).map_err(e))
-            .filter(|r| r.is_err())
-            .next()
-            .unwrap_or(Ok(()))
+        save_edges(
+            name,
+            self.edge_list().filter(|couple| couple.0 < couple.1),
+            self.seed,
+        )
     }
 
     /// Génère les statistiques du graphe comme demandé par l’énoncé.
     pub fn stats(&self) -> Stats {
+        self.stats_with(&StatsOptions::default())
+    }
+    /// Génère les statistiques du graphe avec les statistiques optionnelles de `options`.
+    pub fn stats_with(&self, options: &StatsOptions) -> Stats {
         let before = Instant::now();
 
         let edges = self.edges();
@@ -245,13 +270,57 @@ impl Graph {
             .iter()
             .for_each(|n| degree_distrib[n.len()] += 1);
 
+        let (triangles, clustering_average, transitivity) = match options.clustering {
+            Some(Clustering::Exact) => {
+                let c = self.clustering();
+                (Some(c.triangles), Some(c.average()), Some(c.transitivity()))
+            }
+            Some(Clustering::Sampled { samples, seed }) => {
+                let mut r = rng(seed);
+                let average = self.clustering_average_sampled(samples, &mut r);
+                (
+                    None,
+                    Some(average),
+                    Some(self.transitivity_sampled(samples, &mut r)),
+                )
+            }
+            None => (None, None, None),
+        };
+        let core_distrib = if options.cores {
+            Some(self.cores().distrib())
+        } else {
+            None
+        };
+        let clique_distrib = if options.cliques {
+            Some(self.clique_distrib())
+        } else {
+            None
+        };
+        let graphlets = if options.graphlets {
+            Some(self.graphlet_counts())
+        } else {
+            None
+        };
+        let distances = if options.distances {
+            Some(self.distance_distribution())
+        } else {
+            None
+        };
+
         Stats {
             nodes: self.len(),
-            edges: edges,
+            edges,
             degree_average: ((edges * 2) as f64) / (self.len() as f64),
-            degree_distrib: degree_distrib,
-            degree_max: degree_max,
+            degree_distrib,
+            degree_max,
             distance: self.distance(),
+            triangles,
+            clustering_average,
+            transitivity,
+            core_distrib,
+            clique_distrib,
+            graphlets,
+            distances,
             duration: before.elapsed(),
         }
     }
@@ -259,6 +328,14 @@ impl Graph {
     pub fn len(&self) -> usize {
         self.adjacency_list.len()
     }
+    /// Indique si le graphe n'a aucun sommet. Complexité constante.
+    pub fn is_empty(&self) -> bool {
+        self.adjacency_list.is_empty()
+    }
+    /// Graine ayant servi à générer le graphe, voir `Graph::with_seed`.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
     /// Nombre total d'arêtes. Complexité: O(S).
     pub fn edges(&self) -> usize {
         self.adjacency_list
@@ -306,15 +383,13 @@ impl Graph {
         }
 
         // Récupère les nœuds séléctionnés et mesure le diamètre.
-        (0..self.len())
-            .filter(|n| origins[*n])
-            .inspect(|origin| p.print("diameter", *origin))
-            .for_each(|origin| {
-                let min = subtree[origin];
-                self.bfs(origin, &whitelist, &mut |n, d| {
-                    longest = max(longest, min + d + subtree[n]);
-                });
+        (0..self.len()).filter(|n| origins[*n]).for_each(|origin| {
+            p.print("diameter", origin);
+            let min = subtree[origin];
+            self.bfs(origin, &whitelist, &mut |n, d| {
+                longest = max(longest, min + d + subtree[n]);
             });
+        });
 
         longest
     }
@@ -337,7 +412,7 @@ impl Graph {
                     let d = dist[parent].unwrap_or(0);
                     f(parent, d);
                     let minimum: usize = d + 1;
-                    self.children(parent, &whitelist).for_each(|child| {
+                    self.children(parent, whitelist).for_each(|child| {
                         if dist[child].is_none() {
                             dist[child] = Some(minimum);
                             node_todo.push_back(child);
@@ -416,15 +491,71 @@ impl Graph {
         .copied()
         .filter(move |n| whitelist[*n])
     }
+    /// Liste d'adjacence non orientée triée, sans boucle ni arête multiple: un arc présent dans un
+    /// seul sens est ajouté dans les deux. Complexité: O(S + A log A).
+    pub(crate) fn simple_adjacency(&self) -> Vec<Vec<usize>> {
+        let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); self.len()];
+        for (n, children) in self.adjacency_list.iter().enumerate() {
+            for &c in children.iter().filter(|&&c| c != n) {
+                adjacency[n].push(c);
+                adjacency[c].push(n);
+            }
+        }
+        for c in adjacency.iter_mut() {
+            c.sort_unstable();
+            c.dedup();
+        }
+        adjacency
+    }
+    /// Sous-graphe induit par les sommets `n` tels que `keep[n]`, renumérotés dans l'ordre.
+    /// Retourne le sous-graphe et l'indice d'origine de chacun de ses sommets. Complexité: O(S+A).
+    pub fn subgraph(&self, keep: &[bool]) -> (Graph, Vec<usize>) {
+        let origin: Vec<usize> = (0..self.len()).filter(|&n| keep[n]).collect();
+        let mut index = vec![usize::MAX; self.len()];
+        origin.iter().enumerate().for_each(|(i, &n)| index[n] = i);
+        let adjacency_list = origin
+            .iter()
+            .map(|&n| {
+                self.adjacency_list[n]
+                    .iter()
+                    .filter(|&&c| keep[c])
+                    .map(|&c| index[c])
+                    .collect()
+            })
+            .collect();
+        let g = Graph {
+            adjacency_list,
+            seed: None,
+        };
+        (g, origin)
+    }
     /// Retourne un itérateur avec chaque arrête du graphe.
     pub fn edge_list(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
         self.adjacency_list
             .iter()
             .enumerate()
-            .map(|(p, parent): (usize, _)| parent.iter().map(move |child: &usize| (p, *child)))
-            .flatten()
+            .flat_map(|(p, parent): (usize, _)| parent.iter().map(move |child: &usize| (p, *child)))
     }
 }
+
+/// Éléments communs de deux listes triées, dans l'ordre croissant. Complexité: O(a+b).
+pub(crate) fn intersection<'a>(a: &'a [usize], b: &'a [usize]) -> impl Iterator<Item = usize> + 'a {
+    let (mut i, mut j) = (0, 0);
+    std::iter::from_fn(move || {
+        while i < a.len() && j < b.len() {
+            if a[i] < b[j] {
+                i += 1;
+            } else if a[i] > b[j] {
+                j += 1;
+            } else {
+                i += 1;
+                j += 1;
+                return Some(a[i - 1]);
+            }
+        }
+        None
+    })
+}
 #[test]
 fn graph_add() {
     let mut g = Graph::new(Some(2));
@@ -444,6 +575,18 @@ fn graph_push() {
     assert_eq!(vec![0], g.adjacency_list[1]);
 }
 #[test]
+fn graph_save_seed() {
+    let name = std::env::temp_dir().join("graph_save_seed.csv");
+    let name = name.to_str().unwrap();
+    let g = Graph::with_seed(3, |r| Graph::gen_barabasi_albert(10, r));
+    g.save(name).unwrap();
+
+    let content = std::fs::read_to_string(name).unwrap();
+    assert!(content.starts_with("# seed: 3\nid1,id2\n"));
+    assert_eq!(g.edges(), Graph::load(name, None).unwrap().edges());
+    std::fs::remove_file(name).unwrap();
+}
+#[test]
 fn graph_distance() {
     // Source: https://fr.wikipedia.org/wiki/Matrice_d%27adjacence#Exemples mais non orienté
     let mut g = Graph::new(Some(8));
@@ -470,7 +613,7 @@ fn graph_distance() {
 
     assert_eq!(
         dist,
-        g.bfs(5, &vec![true; 8], &mut |n, d| if dist[n] != Some(d) {
+        g.bfs(5, &[true; 8], &mut |n, d| if dist[n] != Some(d) {
             panic!("Node: {} and distance: {} is wrong", n, d);
         })
     );
@@ -495,6 +638,16 @@ fn graph_children() {
     assert_eq!(vec![0, 1, 2], g.children(5, &w).collect::<Vec<usize>>());
 }
 #[test]
+fn graph_simple_adjacency() {
+    // Arcs dans un seul sens, arête multiple et boucle.
+    let mut g = Graph::new(Some(4));
+    g.adjacency_list[0].extend([2, 1, 1, 0]);
+    g.adjacency_list[3].push(0);
+    g.adjacency_list[1].push(0);
+    let expected = vec![vec![1, 2, 3], vec![0], vec![0], vec![0]];
+    assert_eq!(expected, g.simple_adjacency());
+}
+#[test]
 fn test_mark_tree() {
     let mut g = Graph::new(Some(15));
     g.add((0, 1));
@@ -571,7 +724,7 @@ fn graph_display() {
     g.add((7, 6)); // Double
 
     assert_eq!(
-        vec![
+        [
             "* 1 . . 1 1 . .",
             "1 * . . . 1 1 .",
             ". . * 1 . 1 . .",
@@ -1,8 +1,8 @@
 pub fn csv(s: &str) -> Result<(usize, usize), String> {
-    let v: Vec<&str> = s.split(',').filter(|s| s.len() > 0).take(2).collect();
+    let v: Vec<&str> = s.split(',').filter(|s| !s.is_empty()).take(2).collect();
 
     if v.len() != 2 {
-        return Err(format!("This line has no coma to separate the two usize"));
+        return Err("This line has no coma to separate the two usize".to_string());
     }
 
     Ok((
@@ -20,12 +20,12 @@ fn test_csv() {
 pub fn tab(s: &str) -> Result<(usize, usize), String> {
     let v: Vec<&str> = s
         .split(char::is_whitespace)
-        .filter(|s| s.len() > 0)
+        .filter(|s| !s.is_empty())
         .take(2)
         .collect();
 
     if v.len() != 2 {
-        return Err(format!("This line no contain two usize with is_whitespace"));
+        return Err("This line no contain two usize with is_whitespace".to_string());
     }
 
     Ok((
@@ -41,9 +41,172 @@ fn test_tab() {
 }
 
 pub fn save_csv<W: std::io::Write>(w: &mut W, couple: (usize, usize)) -> std::io::Result<()> {
-    write!(w, "{},{}\n", couple.0, couple.1)
+    writeln!(w, "{},{}", couple.0, couple.1)
 }
 
 pub fn save_txt<W: std::io::Write>(w: &mut W, couple: (usize, usize)) -> std::io::Result<()> {
-    write!(w, "{}\t{}\n", couple.0, couple.1)
+    writeln!(w, "{}\t{}", couple.0, couple.1)
+}
+
+/// Enregistre les arêtes `edges` dans le fichier `name` sans construire de graphe, les arêtes
+/// sont écrites au fur et à mesure; le format est déterminé par les extentions qui penvent être
+/// ".txt" ou bien ".csv". La graine `seed` est inscrite en commentaire au début du fichier. Les
+/// arêtes sont écrites telles quelles, alors que `Graph::save` ne garde que les arcs `(a, b)`
+/// avec `a < b`.
+/// ```
+/// let name = std::env::temp_dir().join("save_edges.txt");
+/// let name = name.to_str().unwrap();
+/// let mut r = graph::rng(4);
+/// graph::save_edges(name, graph::Graph::stream_barabasi_albert(1000, &mut r), Some(4)).unwrap();
+/// let g = graph::Graph::load(name, None).unwrap();
+/// assert_eq!(3 + 997 * 2, g.edges());
+/// # std::fs::remove_file(name).unwrap();
+/// ```
+pub fn save_edges<I>(name: &str, edges: I, seed: Option<u64>) -> Result<(), String>
+where
+    I: Iterator<Item = (usize, usize)>,
+{
+    use std::io::Write;
+    let e = |err| format!("Fail to write into {:?} {}", name, err);
+
+    let (header, writer): (&str, fn(&mut _, _) -> _) = if name.ends_with(".csv") {
+        ("id1,id2", save_csv)
+    } else if name.ends_with(".txt") {
+        ("# FromNodeId\tToNodeId", save_txt)
+    } else {
+        return Err(format!("Unknow extension of the file {:?}", name));
+    };
+
+    let mut file = std::io::BufWriter::new(std::fs::File::create(name).map_err(e)?);
+    if let Some(seed) = seed {
+        writeln!(file, "# seed: {}", seed).map_err(e)?;
+    }
+    writeln!(file, "{}", header).map_err(e)?;
+    for couple in edges {
+        writer(&mut file, couple).map_err(e)?;
+    }
+    file.flush().map_err(e)
+}
+
+/// Enregistre la partition `partition`, qui donne la communauté de chaque sommet, dans le fichier
+/// `name`; le format est déterminé par les extentions qui penvent être ".txt" ou bien ".csv".
+/// Chaque ligne contient un sommet et sa communauté.
+/// ```
+/// let name = std::env::temp_dir().join("save_partition.csv");
+/// let name = name.to_str().unwrap();
+/// graph::save_partition(name, &[0, 0, 1]).unwrap();
+/// assert_eq!(Ok(vec![0, 0, 1]), graph::load_partition(name));
+/// # std::fs::remove_file(name).unwrap();
+/// ```
+pub fn save_partition(name: &str, partition: &[usize]) -> Result<(), String> {
+    use std::io::Write;
+    let e = |err| format!("Fail to write into {:?} {}", name, err);
+
+    let (header, writer): (&str, fn(&mut _, _) -> _) = if name.ends_with(".csv") {
+        ("node,community", save_csv)
+    } else if name.ends_with(".txt") {
+        ("# NodeId\tCommunity", save_txt)
+    } else {
+        return Err(format!("Unknow extension of the file {:?}", name));
+    };
+
+    let mut file = std::io::BufWriter::new(std::fs::File::create(name).map_err(e)?);
+    writeln!(file, "{}", header).map_err(e)?;
+    for couple in partition.iter().copied().enumerate() {
+        writer(&mut file, couple).map_err(e)?;
+    }
+    file.flush().map_err(e)
+}
+
+/// Charge une partition enregistrée par `save_partition`. Les lignes vides et celles commençant
+/// par un croisillon `'#'` sont ignorées, ainsi que l'en-tête des fichiers ".csv". Chaque sommet
+/// de 0 au plus grand sommet lu doit avoir une communauté.
+pub fn load_partition(name: &str) -> Result<Vec<usize>, String> {
+    use std::io::prelude::*;
+    let (parser, header): (fn(&str) -> _, usize) = if name.ends_with(".csv") {
+        (csv, 1)
+    } else if name.ends_with(".txt") {
+        (tab, 0)
+    } else {
+        return Err(format!("Unknow extension of the file {:?}", name));
+    };
+
+    let file =
+        std::fs::File::open(name).map_err(|err| format!("Fail to open {:?} {}", name, err))?;
+    let mut partition: Vec<Option<usize>> = Vec::new();
+    for (num, line) in std::io::BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.as_ref().is_ok_and(|l| l.starts_with('#')))
+        .skip(header)
+    {
+        let line = line.map_err(|err| format!("Fail to read {:?} {}", name, err))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (node, community) =
+            parser(line.trim()).map_err(|err| format!("line {}, parse fail: {}", num, err))?;
+        if node >= partition.len() {
+            partition.resize(node + 1, None);
+        }
+        partition[node] = Some(community);
+    }
+    partition
+        .iter()
+        .enumerate()
+        .map(|(n, c)| c.ok_or_else(|| format!("The node {} has no community", n)))
+        .collect()
+}
+/// Enregistre la partie de chaque sommet dans le fichier `name` au format de METIS: une ligne par
+/// sommet, dans l'ordre, contenant seulement le numéro de sa partie.
+pub fn save_metis_partition(name: &str, parts: &[usize]) -> Result<(), String> {
+    use std::io::Write;
+    let e = |err| format!("Fail to write into {:?} {}", name, err);
+    let mut file = std::io::BufWriter::new(std::fs::File::create(name).map_err(e)?);
+    for p in parts.iter() {
+        writeln!(file, "{}", p).map_err(e)?;
+    }
+    file.flush().map_err(e)
+}
+#[test]
+fn partition_file() {
+    let name = std::env::temp_dir().join("partition_file.txt");
+    let name = name.to_str().unwrap();
+    save_partition(name, &[2, 0, 1, 1]).unwrap();
+    assert_eq!(Ok(vec![2, 0, 1, 1]), load_partition(name));
+
+    std::fs::write(name, "# comment\n0\t1\n\n2\t0\n").unwrap();
+    assert!(load_partition(name).is_err());
+    std::fs::remove_file(name).unwrap();
+    assert!(load_partition("partition.json").is_err());
+
+    let name = std::env::temp_dir().join("partition_file.part.3");
+    save_metis_partition(name.to_str().unwrap(), &[2, 0, 1]).unwrap();
+    assert_eq!("2\n0\n1\n", std::fs::read_to_string(&name).unwrap());
+    std::fs::remove_file(name).unwrap();
+}
+#[test]
+fn save_edges_stream() {
+    use super::{rng, Graph};
+    // Un flux écrit directement donne le même fichier que le graphe généré puis enregistré.
+    let (streamed, generated) = (
+        std::env::temp_dir().join("save_edges_stream.csv"),
+        std::env::temp_dir().join("save_edges_gen.csv"),
+    );
+    let (streamed, generated) = (streamed.to_str().unwrap(), generated.to_str().unwrap());
+    let mut r = rng(1);
+    let edges = Graph::stream_gilbert(40, &mut r).filter(|(i, j)| i < j);
+    save_edges(streamed, edges, Some(1)).unwrap();
+    Graph::with_seed(1, |r| Graph::gen_gilbert(40, r))
+        .save(generated)
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(generated).unwrap(),
+        std::fs::read_to_string(streamed).unwrap()
+    );
+    let g = Graph::load_csv(generated, Some(40)).unwrap();
+    let h = Graph::load_csv(streamed, Some(40)).unwrap();
+    assert_eq!(g.adjacency_list, h.adjacency_list);
+    std::fs::remove_file(streamed).unwrap();
+    std::fs::remove_file(generated).unwrap();
 }
@@ -0,0 +1,401 @@
+use super::gen::shuffle;
+use super::Graph;
+use rand::Rng;
+use std::collections::BinaryHeap;
+
+/// Découpage équilibré des sommets, calculé par `Graph::partition_kway`.
+#[derive(Debug, Clone)]
+pub struct Partitioning {
+    /// Partie de chaque sommet, de 0 à k-1.
+    pub parts: Vec<usize>,
+    /// Nombre d'arêtes entre deux parties différentes.
+    pub edge_cut: usize,
+    /// Taille de la plus grande partie divisée par la taille moyenne, 1 pour un découpage
+    /// parfaitement équilibré.
+    pub imbalance: f64,
+}
+
+/// Graphe pondéré d'un niveau de grossissement: poids des sommets et liste des voisins avec le
+/// poids de l'arête, sans boucle.
+struct Weighted {
+    weight: Vec<usize>,
+    adjacency: Vec<Vec<(usize, usize)>>,
+}
+
+impl Weighted {
+    fn len(&self) -> usize {
+        self.weight.len()
+    }
+    /// Regroupe les sommets par couplage des arêtes lourdes: chaque sommet, dans un ordre
+    /// aléatoire, est fusionné avec son voisin libre relié par l'arête la plus lourde. Retourne
+    /// le graphe grossi et le sommet grossi de chaque sommet.
+    fn coarsen<R: Rng + ?Sized>(&self, r: &mut R) -> (Weighted, Vec<usize>) {
+        let free = usize::MAX;
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        shuffle(r, &mut order);
+        let mut map = vec![free; self.len()];
+        let mut count = 0;
+        for &u in order.iter() {
+            if map[u] != free {
+                continue;
+            }
+            map[u] = count;
+            let heaviest = self.adjacency[u]
+                .iter()
+                .filter(|&&(v, _)| map[v] == free)
+                .max_by_key(|&&(_, w)| w);
+            if let Some(&(v, _)) = heaviest {
+                map[v] = count;
+            }
+            count += 1;
+        }
+        (self.contract(&map, count), map)
+    }
+    /// Fusionne les sommets selon `map`, les arêtes parallèles sont fusionnées en sommant leur
+    /// poids et les arêtes internes disparaissent.
+    fn contract(&self, map: &[usize], count: usize) -> Weighted {
+        let mut weight = vec![0; count];
+        let mut adjacency: Vec<Vec<(usize, usize)>> = vec![Vec::new(); count];
+        for u in 0..self.len() {
+            let cu = map[u];
+            weight[cu] += self.weight[u];
+            adjacency[cu].extend(
+                self.adjacency[u]
+                    .iter()
+                    .filter(|&&(v, _)| map[v] != cu)
+                    .map(|&(v, w)| (map[v], w)),
+            );
+        }
+        for list in adjacency.iter_mut() {
+            list.sort_unstable();
+            let mut merged: Vec<(usize, usize)> = Vec::with_capacity(list.len());
+            for &(v, w) in list.iter() {
+                match merged.last_mut() {
+                    Some(last) if last.0 == v => last.1 += w,
+                    _ => merged.push((v, w)),
+                }
+            }
+            *list = merged;
+        }
+        Weighted { weight, adjacency }
+    }
+    /// Sous-graphe induit par les sommets `nodes`.
+    fn induced(&self, nodes: &[usize]) -> Weighted {
+        let mut index = vec![usize::MAX; self.len()];
+        nodes.iter().enumerate().for_each(|(i, &n)| index[n] = i);
+        Weighted {
+            weight: nodes.iter().map(|&n| self.weight[n]).collect(),
+            adjacency: nodes
+                .iter()
+                .map(|&n| {
+                    self.adjacency[n]
+                        .iter()
+                        .filter(|&&(v, _)| index[v] != usize::MAX)
+                        .map(|&(v, w)| (index[v], w))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+    /// Poids des arêtes entre les deux côtés.
+    fn cut(&self, side: &[usize]) -> usize {
+        (0..self.len())
+            .flat_map(|u| self.adjacency[u].iter().map(move |&(v, w)| (u, v, w)))
+            .filter(|&(u, v, _)| u < v && side[u] != side[v])
+            .map(|(_, _, w)| w)
+            .sum()
+    }
+    /// Coupe en deux côtés, le côté 0 visant le poids `target` et chaque côté pouvant dépasser
+    /// son poids visé de la proportion `tolerance` (ou d'un sommet): le graphe est grossi jusqu'à
+    /// une centaine de sommets, coupé par croissance gloutonne puis chaque niveau est affiné par
+    /// Fiduccia-Mattheyses.
+    fn bisect<R: Rng + ?Sized>(&self, target: usize, tolerance: f64, r: &mut R) -> Vec<usize> {
+        let total: usize = self.weight.iter().sum();
+        let targets = [target, total - target];
+
+        let mut levels: Vec<(Weighted, Vec<usize>)> = Vec::new();
+        loop {
+            let current = levels.last().map_or(self, |(g, _)| g);
+            if current.len() <= 100 {
+                break;
+            }
+            let (coarse, map) = current.coarsen(r);
+            if coarse.len() * 10 > current.len() * 9 {
+                break;
+            }
+            levels.push((coarse, map));
+        }
+
+        let maximum = |g: &Weighted| {
+            let heaviest = g.weight.iter().copied().max().unwrap_or(0);
+            let limit = |t: usize| ((t as f64 * (1.0 + tolerance)) as usize).max(t + heaviest);
+            [limit(targets[0]), limit(targets[1])]
+        };
+
+        // Plusieurs croissances gloutonnes sur le graphe le plus grossier, la meilleure est
+        // gardée.
+        let coarsest = levels.last().map_or(self, |(g, _)| g);
+        let max = maximum(coarsest);
+        let mut side = Vec::new();
+        let mut best = (usize::MAX, usize::MAX);
+        for _ in 0..8 {
+            let mut s = coarsest.grow(target, r);
+            coarsest.refine(&mut s, max);
+            let score = (coarsest.overweight(&s, max), coarsest.cut(&s));
+            if score < best {
+                best = score;
+                side = s;
+            }
+        }
+
+        // Projection sur les niveaux plus fins.
+        for i in (0..levels.len()).rev() {
+            let finer = if i == 0 { self } else { &levels[i - 1].0 };
+            let map = &levels[i].1;
+            side = map.iter().map(|&c| side[c]).collect();
+            finer.refine(&mut side, maximum(finer));
+        }
+        side
+    }
+    /// Côté 0 construit par parcours en largeur depuis un sommet aléatoire jusqu'à atteindre le
+    /// poids `target`.
+    fn grow<R: Rng + ?Sized>(&self, target: usize, r: &mut R) -> Vec<usize> {
+        let mut side = vec![1; self.len()];
+        let mut weight = 0;
+        let mut queue = std::collections::VecDeque::new();
+        let mut seen = vec![false; self.len()];
+        // Sommets dans un ordre aléatoire: le premier non vu après `cursor` est un sommet libre
+        // tiré uniformément.
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        shuffle(r, &mut order);
+        let mut cursor = 0;
+        while weight < target {
+            let u = match queue.pop_front() {
+                Some(u) => u,
+                None => {
+                    // Nouvelle composante: le prochain sommet libre.
+                    while cursor < order.len() && seen[order[cursor]] {
+                        cursor += 1;
+                    }
+                    if cursor == order.len() {
+                        break;
+                    }
+                    let u = order[cursor];
+                    seen[u] = true;
+                    u
+                }
+            };
+            side[u] = 0;
+            weight += self.weight[u];
+            for &(v, _) in self.adjacency[u].iter() {
+                if !seen[v] {
+                    seen[v] = true;
+                    queue.push_back(v);
+                }
+            }
+        }
+        side
+    }
+    /// Poids des côtés.
+    fn weights(&self, side: &[usize]) -> [usize; 2] {
+        let mut w = [0; 2];
+        (0..self.len()).for_each(|n| w[side[n]] += self.weight[n]);
+        w
+    }
+    /// Dépassement total des poids maximaux.
+    fn overweight(&self, side: &[usize], maximum: [usize; 2]) -> usize {
+        let w = self.weights(side);
+        w[0].saturating_sub(maximum[0]) + w[1].saturating_sub(maximum[1])
+    }
+    /// Affinage de Fiduccia-Mattheyses: à chaque passe, les sommets sont déplacés un par un vers
+    /// l'autre côté par gain décroissant en respectant les poids maximaux, puis seul le meilleur
+    /// préfixe de déplacements est conservé.
+    fn refine(&self, side: &mut [usize], maximum: [usize; 2]) {
+        let n = self.len();
+        let mut gain = vec![0i64; n];
+        let mut locked = vec![false; n];
+        let mut moves: Vec<usize> = Vec::new();
+        let mut heap: BinaryHeap<(i64, usize)> = BinaryHeap::new();
+        for _ in 0..8 {
+            let mut weights = self.weights(side);
+            let over =
+                |w: &[usize; 2]| w[0].saturating_sub(maximum[0]) + w[1].saturating_sub(maximum[1]);
+            heap.clear();
+            for u in 0..n {
+                locked[u] = false;
+                gain[u] = self.adjacency[u]
+                    .iter()
+                    .map(|&(v, w)| {
+                        if side[v] == side[u] {
+                            -(w as i64)
+                        } else {
+                            w as i64
+                        }
+                    })
+                    .sum();
+                if self.adjacency[u].iter().any(|&(v, _)| side[v] != side[u]) || over(&weights) > 0
+                {
+                    heap.push((gain[u], u));
+                }
+            }
+
+            let start = (over(&weights), 0i64);
+            let mut best = start;
+            let mut cut_delta = 0i64;
+            let mut best_len = 0;
+            moves.clear();
+            while let Some((g, u)) = heap.pop() {
+                if locked[u] || g != gain[u] {
+                    continue;
+                }
+                let (from, to) = (side[u], 1 - side[u]);
+                if weights[to] + self.weight[u] > maximum[to] {
+                    continue;
+                }
+                side[u] = to;
+                weights[from] -= self.weight[u];
+                weights[to] += self.weight[u];
+                locked[u] = true;
+                cut_delta -= g;
+                moves.push(u);
+                for &(v, w) in self.adjacency[u].iter() {
+                    if !locked[v] {
+                        gain[v] += if side[v] == to { -2 } else { 2 } * w as i64;
+                        heap.push((gain[v], v));
+                    }
+                }
+                let score = (over(&weights), cut_delta);
+                if score < best {
+                    best = score;
+                    best_len = moves.len();
+                }
+                // Les longues suites de déplacements sans amélioration sont abandonnées.
+                if moves.len() > best_len + 50 + n / 20 {
+                    break;
+                }
+            }
+
+            for &u in moves[best_len..].iter() {
+                side[u] = 1 - side[u];
+            }
+            if best >= start {
+                break;
+            }
+        }
+    }
+}
+
+impl Graph {
+    /// Découpe les sommets en `k` parties de tailles équilibrées en minimisant le nombre d'arêtes
+    /// coupées, par bissections récursives multiniveaux: le graphe est grossi par couplage des
+    /// arêtes lourdes, coupé en deux sur le plus petit niveau, puis la coupe est affinée par
+    /// Fiduccia-Mattheyses à chaque niveau. Chaque partie peut dépasser la taille moyenne d'au
+    /// plus la proportion `tolerance` (ou d'un sommet). Les boucles et les arêtes multiples sont
+    /// ignorées.
+    /// ```
+    /// let g = graph::Graph::grid(&[20, 20]);
+    /// let p = g.partition_kway(4, 0.03, &mut graph::rng(0)).unwrap();
+    /// assert!(p.edge_cut <= 60);
+    /// assert!(p.imbalance <= 1.03);
+    /// ```
+    pub fn partition_kway<R: Rng + ?Sized>(
+        &self,
+        k: usize,
+        tolerance: f64,
+        r: &mut R,
+    ) -> Result<Partitioning, String> {
+        if k == 0 || k > self.len() {
+            return Err(format!(
+                "Can not partition {} nodes into {} parts",
+                self.len(),
+                k
+            ));
+        }
+        let adjacency = self.simple_adjacency();
+        let g = Weighted {
+            weight: vec![1; self.len()],
+            adjacency: adjacency
+                .iter()
+                .map(|c| c.iter().map(|&v| (v, 1)).collect())
+                .collect(),
+        };
+
+        // La tolérance est répartie entre les niveaux de bissection.
+        let depth = (k as f64).log2().ceil().max(1.0);
+        let tolerance = (1.0 + tolerance).powf(1.0 / depth) - 1.0;
+        let mut parts = vec![0; self.len()];
+        let nodes: Vec<usize> = (0..self.len()).collect();
+        let mut todo = vec![(g, nodes, k, 0)];
+        while let Some((g, nodes, k, first)) = todo.pop() {
+            if k == 1 {
+                nodes.iter().for_each(|&n| parts[n] = first);
+                continue;
+            }
+            let k0 = k / 2;
+            let target = (g.len() * k0 + k / 2) / k;
+            let side = g.bisect(target, tolerance, r);
+            let split =
+                |s: usize| -> Vec<usize> { (0..g.len()).filter(|&n| side[n] == s).collect() };
+            let (left, right) = (split(0), split(1));
+            todo.push((
+                g.induced(&right),
+                right.iter().map(|&n| nodes[n]).collect(),
+                k - k0,
+                first + k0,
+            ));
+            todo.push((
+                g.induced(&left),
+                left.iter().map(|&n| nodes[n]).collect(),
+                k0,
+                first,
+            ));
+        }
+
+        let edge_cut = adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(u, c)| c.iter().map(move |&v| (u, v)))
+            .filter(|&(u, v)| u < v && parts[u] != parts[v])
+            .count();
+        let mut sizes = vec![0; k];
+        parts.iter().for_each(|&p| sizes[p] += 1);
+        let largest = sizes.iter().copied().max().unwrap_or(0);
+        Ok(Partitioning {
+            parts,
+            edge_cut,
+            imbalance: largest as f64 * k as f64 / self.len() as f64,
+        })
+    }
+}
+#[test]
+fn test_partition_kway() {
+    use super::rng;
+    // Deux cliques reliées par une arête.
+    let mut g = Graph::complete(10);
+    for (a, b) in Graph::complete(10).edge_list().filter(|(a, b)| a < b) {
+        g.push((a + 10, b + 10));
+    }
+    g.push((9, 10));
+    let p = g.partition_kway(2, 0.0, &mut rng(0)).unwrap();
+    assert_eq!(1, p.edge_cut);
+    assert_eq!(1.0, p.imbalance);
+    assert!(p.parts[..10].iter().all(|&x| x == p.parts[0]));
+
+    // Les groupes d'un modèle à communautés sont retrouvés.
+    let (g, membership) = Graph::gen_planted_partition(6, 50, 0.3, 0.005, &mut rng(1));
+    let p = g.partition_kway(6, 0.05, &mut rng(2)).unwrap();
+    let inter = g
+        .edge_list()
+        .filter(|&(a, b)| a < b && membership[a] != membership[b])
+        .count();
+    assert!(p.edge_cut <= inter + 5, "{} > {}", p.edge_cut, inter);
+    assert!(p.imbalance <= 1.05);
+
+    let g = Graph::grid(&[40, 40]);
+    let p = g.partition_kway(5, 0.03, &mut rng(3)).unwrap();
+    assert_eq!(Some(&4), p.parts.iter().max());
+    assert!(p.imbalance <= 1.03);
+    assert!(p.edge_cut < 200, "{}", p.edge_cut);
+
+    assert!(g.partition_kway(0, 0.03, &mut rng(3)).is_err());
+}
@@ -0,0 +1,314 @@
+use super::Stats;
+use rand::Rng;
+
+/// Loi de puissance discrète P(d) = d^-alpha / ζ(alpha, x_min) pour d >= x_min, ajustée par
+/// `Stats::power_law`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerLaw {
+    /// Exposant estimé par maximum de vraisemblance.
+    pub alpha: f64,
+    /// Plus petit degré suivant la loi.
+    pub x_min: usize,
+    /// Distance de Kolmogorov-Smirnov entre la queue observée et la loi.
+    pub ks: f64,
+    /// Nombre de sommets de degré supérieur ou égal à `x_min`.
+    pub tail: usize,
+}
+
+/// Nombre minimal de valeurs dans la queue pour qu'un `x_min` soit candidat.
+const MIN_TAIL: usize = 10;
+
+/// Fonction zêta de Hurwitz ζ(s, q) = somme des (q+k)^-s pour k >= 0, avec s > 1: les
+/// premiers termes sont sommés et le reste est approché par la formule d'Euler-Maclaurin.
+fn hurwitz_zeta(s: f64, q: f64) -> f64 {
+    // B_2j / (2j)!
+    const BERNOULLI: [f64; 5] = [
+        1.0 / 12.0,
+        -1.0 / 720.0,
+        1.0 / 30240.0,
+        -1.0 / 1209600.0,
+        1.0 / 47900160.0,
+    ];
+    let n = 10;
+    let mut sum: f64 = (0..n).map(|k| (q + k as f64).powf(-s)).sum();
+    let a = q + n as f64;
+    sum += a.powf(1.0 - s) / (s - 1.0) + a.powf(-s) / 2.0;
+    // Produit s(s+1)...(s+2j-2) a^(-s-2j+1).
+    let mut factor = s * a.powf(-s - 1.0);
+    for (j, b) in BERNOULLI.iter().enumerate() {
+        sum += b * factor;
+        let k = 2.0 * j as f64;
+        factor *= (s + k + 1.0) * (s + k + 2.0) / (a * a);
+    }
+    sum
+}
+
+/// Exposant maximisant la vraisemblance de `count` valeurs supérieures ou égales à `x_min`
+/// dont la somme des logarithmes est `logs`, par recherche du nombre d'or sur ]1, 10].
+fn estimate_alpha(x_min: usize, count: usize, logs: f64) -> f64 {
+    let likelihood = |a: f64| -(count as f64) * hurwitz_zeta(a, x_min as f64).ln() - a * logs;
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut lo, mut hi) = (1.0001, 10.0);
+    let mut x1 = hi - ratio * (hi - lo);
+    let mut x2 = lo + ratio * (hi - lo);
+    let (mut f1, mut f2) = (likelihood(x1), likelihood(x2));
+    while hi - lo > 1e-7 {
+        if f1 < f2 {
+            lo = x1;
+            x1 = x2;
+            f1 = f2;
+            x2 = lo + ratio * (hi - lo);
+            f2 = likelihood(x2);
+        } else {
+            hi = x2;
+            x2 = x1;
+            f2 = f1;
+            x1 = hi - ratio * (hi - lo);
+            f1 = likelihood(x1);
+        }
+    }
+    (lo + hi) / 2.0
+}
+
+/// Distance de Kolmogorov-Smirnov entre les valeurs triées `tail` et la loi de puissance.
+fn ks_distance(tail: &[usize], alpha: f64, x_min: usize) -> f64 {
+    let total = hurwitz_zeta(alpha, x_min as f64);
+    let cdf = |x: usize| 1.0 - hurwitz_zeta(alpha, (x + 1) as f64) / total;
+    let n = tail.len() as f64;
+    let mut distance: f64 = 0.0;
+    let mut i = 0;
+    while i < tail.len() {
+        let x = tail[i];
+        // Avant x, la fonction empirique vaut i/n et la loi atteint cdf(x-1).
+        if x > x_min {
+            distance = distance.max((cdf(x - 1) - i as f64 / n).abs());
+        }
+        while i < tail.len() && tail[i] == x {
+            i += 1;
+        }
+        distance = distance.max((cdf(x) - i as f64 / n).abs());
+    }
+    distance
+}
+
+/// Tire une valeur de la loi de puissance discrète par inversion de sa fonction de
+/// répartition: l'intervalle contenant la valeur est doublé puis coupé en deux.
+fn sample_power_law<R: Rng + ?Sized>(alpha: f64, x_min: usize, r: &mut R) -> usize {
+    let total = hurwitz_zeta(alpha, x_min as f64);
+    let v = 1.0 - r.gen::<f64>();
+    // Plus grand x tel que P(X >= x) >= v.
+    let above = |x: usize| hurwitz_zeta(alpha, x as f64) / total >= v;
+    let (mut lo, mut hi) = (x_min, 2 * x_min);
+    while above(hi) && hi < 1 << 40 {
+        lo = hi;
+        hi *= 2;
+    }
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if above(mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Ajuste la loi de puissance sur les valeurs triées `tail`, toutes supérieures ou égales à la
+/// première, dont la somme des logarithmes est `logs`.
+fn fit_tail(tail: &[usize], logs: f64) -> PowerLaw {
+    let x_min = tail[0];
+    let alpha = estimate_alpha(x_min, tail.len(), logs);
+    PowerLaw {
+        alpha,
+        x_min,
+        ks: ks_distance(tail, alpha, x_min),
+        tail: tail.len(),
+    }
+}
+
+/// Ajuste la loi de puissance sur les valeurs triées par ordre croissant: pour chaque `x_min`
+/// candidat, l'exposant est estimé et le `x_min` gardé est celui de plus petite distance de
+/// Kolmogorov-Smirnov.
+fn fit(sorted: &[usize]) -> Option<PowerLaw> {
+    // suffix[i] = somme des logarithmes à partir de i.
+    let mut suffix = vec![0.0; sorted.len() + 1];
+    for i in (0..sorted.len()).rev() {
+        suffix[i] = suffix[i + 1] + (sorted[i] as f64).ln();
+    }
+    let mut best: Option<PowerLaw> = None;
+    let mut i = sorted.iter().position(|&x| x > 0)?;
+    while sorted.len() - i >= MIN_TAIL {
+        let x_min = sorted[i];
+        let law = fit_tail(&sorted[i..], suffix[i]);
+        if best.is_none_or(|b| law.ks < b.ks) {
+            best = Some(law);
+        }
+        while i < sorted.len() && sorted[i] == x_min {
+            i += 1;
+        }
+    }
+    best
+}
+
+impl Stats {
+    /// Ajuste une loi de puissance discrète sur la queue de `degree_distrib` avec la méthode de
+    /// Clauset, Shalizi et Newman: pour chaque `x_min` laissant au moins 10 sommets dans la
+    /// queue, l'exposant est estimé par maximum de vraisemblance (avec la fonction zêta de
+    /// Hurwitz), et le `x_min` gardé minimise la distance de Kolmogorov-Smirnov entre la queue
+    /// et la loi. Échoue si moins de 10 sommets ont un degré non nul.
+    pub fn power_law(&self) -> Result<PowerLaw, String> {
+        fit(&self.degree_sequence())
+            .ok_or_else(|| format!("Less than {} nodes with a positive degree", MIN_TAIL))
+    }
+    /// Comme `power_law` avec un `x_min` imposé, utile quand les petits degrés s'écartent
+    /// de la loi. Échoue si aucun sommet n'a un degré supérieur ou égal à `x_min`.
+    pub fn power_law_from(&self, x_min: usize) -> Result<PowerLaw, String> {
+        let degrees = self.degree_sequence();
+        let tail = &degrees[degrees.partition_point(|&d| d < x_min.max(1))..];
+        if tail.is_empty() {
+            return Err(format!("No node with a degree of at least {}", x_min));
+        }
+        let logs = tail.iter().map(|&d| (d as f64).ln()).sum();
+        Ok(fit_tail(tail, logs))
+    }
+    /// Valeur p de l'ajustement `law` par la méthode de Clauset, Shalizi et Newman: `samples`
+    /// jeux de données synthétiques de même taille sont tirés (la loi de puissance pour une part
+    /// `law.tail` des valeurs, les degrés observés inférieurs à `x_min` pour le reste) et
+    /// ajustés de la même façon. La valeur p est la proportion de jeux dont la distance de
+    /// Kolmogorov-Smirnov dépasse celle observée; la loi de puissance est plausible si elle
+    /// dépasse 0.1.
+    pub fn power_law_p_value<R: Rng + ?Sized>(
+        &self,
+        law: &PowerLaw,
+        samples: usize,
+        r: &mut R,
+    ) -> f64 {
+        let degrees = self.degree_sequence();
+        let body: Vec<usize> = degrees.iter().copied().filter(|&d| d < law.x_min).collect();
+        let tail_ratio = law.tail as f64 / degrees.len() as f64;
+        let mut worse = 0;
+        for _ in 0..samples {
+            let mut sample: Vec<usize> = (0..degrees.len())
+                .map(|_| {
+                    if body.is_empty() || r.gen::<f64>() < tail_ratio {
+                        sample_power_law(law.alpha, law.x_min, r)
+                    } else {
+                        body[super::gen::below(r, body.len())]
+                    }
+                })
+                .collect();
+            sample.sort_unstable();
+            if fit(&sample).is_none_or(|f| f.ks >= law.ks) {
+                worse += 1;
+            }
+        }
+        worse as f64 / samples as f64
+    }
+    /// Fonction de répartition complémentaire des degrés: la proportion de sommets de degré
+    /// supérieur ou égal à chaque degré. Longueur = degree_max+1
+    pub fn ccdf(&self) -> Vec<f64> {
+        let mut ccdf = vec![0.0; self.degree_distrib.len()];
+        let mut above = 0;
+        for d in (0..self.degree_distrib.len()).rev() {
+            above += self.degree_distrib[d];
+            ccdf[d] = above as f64 / self.nodes as f64;
+        }
+        ccdf
+    }
+    /// Moment d'ordre `k` des degrés: la moyenne de d^k.
+    pub fn degree_moment(&self, k: i32) -> f64 {
+        self.degree_distrib
+            .iter()
+            .enumerate()
+            .map(|(d, &nb)| nb as f64 * (d as f64).powi(k))
+            .sum::<f64>()
+            / self.nodes as f64
+    }
+    /// Centile `p` des degrés (entre 0 et 100): le plus petit degré tel qu'au moins p% des
+    /// sommets ont un degré inférieur ou égal.
+    /// ```
+    /// let s = graph::Graph::star(5).stats();
+    /// assert_eq!(1, s.degree_percentile(50.0));
+    /// assert_eq!(4, s.degree_percentile(100.0));
+    /// ```
+    pub fn degree_percentile(&self, p: f64) -> usize {
+        let rank = (p / 100.0 * self.nodes as f64).ceil().max(1.0) as usize;
+        let mut seen = 0;
+        for (d, &nb) in self.degree_distrib.iter().enumerate() {
+            seen += nb;
+            if seen >= rank {
+                return d;
+            }
+        }
+        self.degree_max
+    }
+    /// Coefficient de Gini des degrés: 0 si tous les sommets ont le même degré, proche de 1
+    /// si quelques sommets concentrent les arêtes.
+    pub fn degree_gini(&self) -> f64 {
+        let degrees = self.degree_sequence();
+        let n = degrees.len() as f64;
+        let total = degrees.iter().sum::<usize>() as f64;
+        if total == 0.0 {
+            return 0.0;
+        }
+        degrees
+            .iter()
+            .enumerate()
+            .map(|(i, &d)| (2.0 * (i + 1) as f64 - n - 1.0) * d as f64)
+            .sum::<f64>()
+            / (n * total)
+    }
+}
+#[test]
+fn test_hurwitz_zeta() {
+    let pi2 = std::f64::consts::PI * std::f64::consts::PI;
+    assert!((hurwitz_zeta(2.0, 1.0) - pi2 / 6.0).abs() < 1e-12);
+    assert!((hurwitz_zeta(2.0, 3.0) - (pi2 / 6.0 - 1.25)).abs() < 1e-12);
+    assert!((hurwitz_zeta(3.5, 2.0) - (1.126_733_867_317_057_4 - 1.0)).abs() < 1e-12);
+}
+#[test]
+fn test_power_law() {
+    use super::Graph;
+    let g = Graph::gen_barabasi_albert(5000, &mut super::rng(1));
+    let s = g.stats();
+    // Pour les petits degrés P(d) est proportionnel à 1/(d(d+1)(d+2)), l'exposant 3 n'est
+    // atteint que dans la queue.
+    let law = s.power_law_from(10).unwrap();
+    assert!((law.alpha - 3.0).abs() < 0.3, "{:?}", law);
+    let law = s.power_law().unwrap();
+    assert!(law.alpha > 2.4 && law.alpha < 3.3, "{:?}", law);
+
+    // Modèle de configuration avec des degrés tirés d'une loi de puissance.
+    let mut r = super::rng(2);
+    let mut degrees: Vec<usize> = (0..2000)
+        .map(|_| sample_power_law(2.5, 2, &mut r))
+        .collect();
+    degrees[0] += degrees.iter().sum::<usize>() % 2;
+    let g = Graph::gen_configuration(&degrees, super::Configuration::Multi, &mut r).unwrap();
+    let s = g.stats();
+    let law = s.power_law().unwrap();
+    assert!((law.alpha - 2.5).abs() < 0.2, "{:?}", law);
+    assert!(s.power_law_p_value(&law, 20, &mut r) > 0.1);
+
+    // Les degrés d'un graphe aléatoire de Gilbert ne suivent pas une loi de puissance.
+    let s = Graph::gen_planted_partition(1, 300, 0.5, 0.5, &mut super::rng(3))
+        .0
+        .stats();
+    let law = s.power_law().unwrap();
+    assert!(s.power_law_p_value(&law, 20, &mut super::rng(4)) < 0.1);
+    assert!(Graph::complete(5).stats().power_law().is_err());
+}
+#[test]
+fn test_degree_summary() {
+    use super::Graph;
+    let s = Graph::star(5).stats();
+    assert_eq!(vec![1.0, 1.0, 0.2, 0.2, 0.2], s.ccdf());
+    assert!((s.degree_moment(1) - s.degree_average).abs() < 1e-12);
+    assert!((s.degree_moment(2) - 20.0 / 5.0).abs() < 1e-12);
+    assert_eq!(1, s.degree_percentile(80.0));
+    assert_eq!(4, s.degree_percentile(81.0));
+    assert_eq!(0.0, Graph::cycle(6).stats().degree_gini());
+    // Degrés 1, 1, 1, 1, 4: (2*1-6 + 2*2-6 + 2*3-6 + 2*4-6 + 4*(2*5-6)) / (5*8).
+    assert!((s.degree_gini() - 0.3).abs() < 1e-12);
+}
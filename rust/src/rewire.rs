@@ -0,0 +1,208 @@
+use super::gen::below;
+use super::Graph;
+use rand::Rng;
+use std::collections::VecDeque;
+
+/// Comparaison de statistiques à un modèle nul, calculée par `Graph::z_scores`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZScores {
+    /// Valeur de chaque statistique sur le graphe.
+    pub observed: Vec<f64>,
+    /// Moyenne de chaque statistique sur les graphes aléatoires.
+    pub mean: Vec<f64>,
+    /// Écart type (non biaisé) de chaque statistique sur les graphes aléatoires.
+    pub deviation: Vec<f64>,
+    /// Écart à la moyenne en nombre d'écarts types, `None` si l'écart type est nul.
+    pub z: Vec<Option<f64>>,
+}
+
+/// Indique si `b` est accessible depuis `a`, par un parcours en largeur interrompu dès que `b`
+/// est atteint.
+fn reachable(adjacency: &[Vec<usize>], a: usize, b: usize, seen: &mut Vec<bool>) -> bool {
+    seen.clear();
+    seen.resize(adjacency.len(), false);
+    let mut queue = VecDeque::new();
+    seen[a] = true;
+    queue.push_back(a);
+    while let Some(u) = queue.pop_front() {
+        for &v in adjacency[u].iter() {
+            if v == b {
+                return true;
+            }
+            if !seen[v] {
+                seen[v] = true;
+                queue.push_back(v);
+            }
+        }
+    }
+    a == b
+}
+
+fn remove(c: &mut Vec<usize>, x: usize) {
+    if let Ok(i) = c.binary_search(&x) {
+        c.remove(i);
+    }
+}
+
+fn insert(c: &mut Vec<usize>, x: usize) {
+    if let Err(i) = c.binary_search(&x) {
+        c.insert(i, x);
+    }
+}
+
+impl Graph {
+    /// Graphe aléatoire de même suite de degrés obtenu par `swaps` échanges de Maslov et
+    /// Sneppen: deux arêtes (a, b) et (c, d) choisies au hasard deviennent (a, d) et (c, b), si
+    /// cela ne crée ni boucle ni arête multiple. Avec `connected`, un échange qui sépare une
+    /// composante connexe est annulé (un parcours en largeur par échange). Abandonne après
+    /// `10 * swaps` tentatives. Les boucles et les arêtes multiples sont ignorées.
+    /// Complexité: O(swaps * degré maximal), O(swaps * (S+A)) avec `connected`.
+    /// ```
+    /// let g = graph::Graph::petersen();
+    /// let h = graph::Graph::with_seed(1, |r| g.rewire(100, true, r));
+    /// assert_eq!(g.stats().degree_distrib, h.stats().degree_distrib);
+    /// ```
+    pub fn rewire<R: Rng + ?Sized>(&self, swaps: usize, connected: bool, r: &mut R) -> Graph {
+        let mut adjacency = self.simple_adjacency();
+        let mut edges: Vec<(usize, usize)> = adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(u, c)| c.iter().filter(move |&&v| v > u).map(move |&v| (u, v)))
+            .collect();
+        let mut seen = Vec::new();
+        let mut done = 0;
+        for _ in 0..10 * swaps {
+            if done == swaps || edges.len() < 2 {
+                break;
+            }
+            let (i, j) = (below(r, edges.len()), below(r, edges.len()));
+            let (a, b) = edges[i];
+            let (c, d) = if r.gen() {
+                edges[j]
+            } else {
+                (edges[j].1, edges[j].0)
+            };
+            if a == d
+                || c == b
+                || adjacency[a].binary_search(&d).is_ok()
+                || adjacency[c].binary_search(&b).is_ok()
+            {
+                continue;
+            }
+            let swap = |adjacency: &mut Vec<Vec<usize>>, (a, b, c, d)| {
+                remove(&mut adjacency[a], b);
+                remove(&mut adjacency[b], a);
+                remove(&mut adjacency[c], d);
+                remove(&mut adjacency[d], c);
+                insert(&mut adjacency[a], d);
+                insert(&mut adjacency[d], a);
+                insert(&mut adjacency[c], b);
+                insert(&mut adjacency[b], c);
+            };
+            swap(&mut adjacency, (a, b, c, d));
+            // Les composantes restent connexes si les extrémités des arêtes retirées sont
+            // encore reliées.
+            if connected
+                && !(reachable(&adjacency, a, b, &mut seen)
+                    && reachable(&adjacency, c, d, &mut seen))
+            {
+                swap(&mut adjacency, (a, d, c, b));
+                continue;
+            }
+            edges[i] = (a, d);
+            edges[j] = (c, b);
+            done += 1;
+        }
+        Graph {
+            adjacency_list: adjacency,
+            seed: None,
+        }
+    }
+    /// Compare les statistiques `statistic` du graphe à leur distribution sur `samples` graphes
+    /// obtenus par `rewire(swaps, connected)`: une statistique dont le z est grand en valeur
+    /// absolue (au-delà de 2 ou 3) n'est pas expliquée par la seule suite de degrés.
+    /// ```
+    /// use graph::Graph;
+    /// // Les triangles d'un graphe géométrique.
+    /// let g = Graph::with_seed(1, |r| Graph::gen_geometric(200, 0.1, false, r).0);
+    /// let mut r = graph::rng(2);
+    /// let z = g.z_scores(20, 2000, false, &mut r, |g| vec![g.clustering().triangles as f64]);
+    /// assert!(z.z[0].unwrap() > 3.0);
+    /// ```
+    pub fn z_scores<R, F>(
+        &self,
+        samples: usize,
+        swaps: usize,
+        connected: bool,
+        r: &mut R,
+        mut statistic: F,
+    ) -> ZScores
+    where
+        R: Rng + ?Sized,
+        F: FnMut(&Graph) -> Vec<f64>,
+    {
+        let observed = statistic(self);
+        let mut sum = vec![0.0; observed.len()];
+        let mut square = vec![0.0; observed.len()];
+        for _ in 0..samples {
+            let values = statistic(&self.rewire(swaps, connected, r));
+            for (i, x) in values.iter().enumerate() {
+                sum[i] += x;
+                square[i] += x * x;
+            }
+        }
+        let n = samples as f64;
+        let mean: Vec<f64> = sum.iter().map(|s| s / n).collect();
+        let deviation: Vec<f64> = square
+            .iter()
+            .zip(mean.iter())
+            .map(|(sq, m)| ((sq - n * m * m) / (n - 1.0)).max(0.0).sqrt())
+            .collect();
+        let z = observed
+            .iter()
+            .zip(mean.iter().zip(deviation.iter()))
+            .map(|(x, (m, s))| if *s > 0.0 { Some((x - m) / s) } else { None })
+            .collect();
+        ZScores {
+            observed,
+            mean,
+            deviation,
+            z,
+        }
+    }
+}
+#[test]
+fn test_rewire() {
+    let g = Graph::gen_barabasi_albert(300, &mut super::rng(1));
+    let adjacency = g.simple_adjacency();
+    for &connected in [false, true].iter() {
+        let h = g.rewire(2000, connected, &mut super::rng(2));
+        let rewired = h.simple_adjacency();
+        for (a, b) in adjacency.iter().zip(rewired.iter()) {
+            assert_eq!(a.len(), b.len());
+        }
+        // Graphe simple, différent de l'original.
+        assert_eq!(rewired, h.adjacency_list);
+        assert_ne!(adjacency, rewired);
+        if connected {
+            let seen = h.bfs(0, &vec![true; h.len()], &mut |_, _| {});
+            assert!(seen.iter().all(|d| d.is_some()));
+        }
+    }
+
+    // Un arbre reste un arbre.
+    let tree = Graph::kary_tree(2, 6);
+    let h = tree.rewire(500, true, &mut super::rng(3));
+    let seen = h.bfs(0, &vec![true; h.len()], &mut |_, _| {});
+    assert!(seen.iter().all(|d| d.is_some()));
+    assert_eq!(tree.edges(), h.edges());
+
+    // Le nombre d'arêtes (sans les arêtes multiples) ne varie pas.
+    let z = g.z_scores(5, 100, false, &mut super::rng(4), |g| {
+        vec![g.edges() as f64, g.assortativity()]
+    });
+    let simple = adjacency.iter().map(|c| c.len()).sum::<usize>() / 2;
+    assert_eq!(simple as f64, z.mean[0]);
+    assert_eq!(None, z.z[0]);
+    assert!(z.z[1].is_some());
+}
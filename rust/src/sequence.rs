@@ -0,0 +1,275 @@
+use super::gen::{shuffle, skip};
+use super::{Graph, Stats};
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Variante du modèle de configuration, voir `Graph::gen_configuration`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Configuration {
+    /// Multigraphe: les boucles et les arêtes multiples sont conservées.
+    Multi,
+    /// Les boucles et les arêtes multiples sont retirées, les degrés peuvent donc diminuer.
+    Erased,
+    /// Recommence l'appariement jusqu'à obtenir un graphe simple, au plus le nombre de fois
+    /// indiqué.
+    Rejection(usize),
+}
+
+/// Indique si la suite de degrés `degrees` peut être celle d'un graphe simple, avec le théorème
+/// d'Erdős-Gallai. Complexité: O(S log S).
+/// ```
+/// assert!(graph::is_graphical(&[3, 3, 2, 2, 2]));
+/// assert!(!graph::is_graphical(&[3, 3, 1, 1]));
+/// ```
+pub fn is_graphical(degrees: &[usize]) -> bool {
+    let mut d = degrees.to_vec();
+    d.sort_unstable_by(|a, b| b.cmp(a));
+    let n = d.len();
+    if d.iter().sum::<usize>() % 2 == 1 || d.first().is_some_and(|&m| m >= n) {
+        return false;
+    }
+
+    // suffix[i] = somme des degrés à partir de i.
+    let mut suffix = vec![0; n + 1];
+    for i in (0..n).rev() {
+        suffix[i] = suffix[i + 1] + d[i];
+    }
+
+    // Nombre de degrés supérieurs ou égaux à k.
+    let mut p = n;
+    for k in 1..=n {
+        while p > 0 && d[p - 1] < k {
+            p -= 1;
+        }
+        let left = suffix[0] - suffix[k];
+        let right = k * (k - 1) + k * p.saturating_sub(k) + suffix[p.max(k)];
+        if left > right {
+            return false;
+        }
+    }
+
+    true
+}
+
+impl Graph {
+    /// Génère un graphe aléatoire dont le sommet `i` a le degré `degrees[i]` avec le modèle de
+    /// configuration: chaque sommet a autant de demi-arêtes que son degré, qui sont appariées
+    /// au hasard. Échoue si la somme des degrés est impaire ou si aucun graphe simple n'a été
+    /// trouvé en mode `Rejection`.
+    pub fn gen_configuration<R: Rng + ?Sized>(
+        degrees: &[usize],
+        mode: Configuration,
+        r: &mut R,
+    ) -> Result<Graph, String> {
+        let edges = Graph::stream_configuration(degrees, mode, r)?;
+        Ok(Graph::new_iter(edges, Some(degrees.len())))
+    }
+    /// Arêtes du modèle de configuration, voir `gen_configuration`. Les demi-arêtes sont
+    /// appariées (et les tentatives du mode `Rejection` faites) avant de retourner l'itérateur.
+    pub fn stream_configuration<R: Rng + ?Sized>(
+        degrees: &[usize],
+        mode: Configuration,
+        r: &mut R,
+    ) -> Result<impl Iterator<Item = (usize, usize)>, String> {
+        if degrees.iter().sum::<usize>() % 2 == 1 {
+            return Err("The sum of the degrees is odd".to_string());
+        }
+        let mut stubs: Vec<usize> = Vec::with_capacity(degrees.iter().sum());
+        for (n, &d) in degrees.iter().enumerate() {
+            stubs.extend(std::iter::repeat_n(n, d));
+        }
+
+        let attempts = match mode {
+            Configuration::Rejection(attempts) => attempts,
+            _ => 1,
+        };
+        let simple = |stubs: &[usize]| {
+            let mut seen: HashSet<(usize, usize)> = HashSet::with_capacity(stubs.len() / 2);
+            stubs
+                .chunks_exact(2)
+                .all(|pair| pair[0] != pair[1] && seen.insert(ordered(pair)))
+        };
+        let mut found = false;
+        for _ in 0..attempts {
+            shuffle(r, &mut stubs);
+            if !matches!(mode, Configuration::Rejection(_)) || simple(&stubs) {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            return Err(format!("No simple graph found in {} attempts", attempts));
+        }
+
+        let mut seen: HashSet<(usize, usize)> = HashSet::new();
+        let mut pairs = stubs.into_iter();
+        Ok(std::iter::from_fn(move || loop {
+            let pair = [pairs.next()?, pairs.next()?];
+            let (a, b) = ordered(&pair);
+            if mode == Configuration::Erased && (a == b || !seen.insert((a, b))) {
+                continue;
+            }
+            return Some((a, b));
+        }))
+    }
+    /// Construit un graphe simple avec la suite de degrés `degrees` par l'algorithme de
+    /// Havel-Hakimi: le sommet de plus grand degré restant est relié aux sommets suivants de plus
+    /// grands degrés restants. Échoue si la suite n'est pas graphique. Complexité: O(S²).
+    pub fn havel_hakimi(degrees: &[usize]) -> Result<Graph, String> {
+        let edges = Graph::stream_havel_hakimi(degrees)?;
+        Ok(Graph::new_iter(edges, Some(degrees.len())))
+    }
+    /// Arêtes du graphe de Havel-Hakimi, voir `havel_hakimi`. La suite est vérifiée avec le
+    /// théorème d'Erdős-Gallai avant de retourner l'itérateur. Mémoire utilisée: O(S).
+    pub fn stream_havel_hakimi(
+        degrees: &[usize],
+    ) -> Result<impl Iterator<Item = (usize, usize)>, String> {
+        if !is_graphical(degrees) {
+            return Err("The degree sequence is not graphical".to_string());
+        }
+        // Couples (degré restant, sommet), triés par degré décroissant.
+        let mut rest: Vec<(usize, usize)> = degrees.iter().copied().zip(0..).collect();
+        rest.sort_by(|a, b| b.cmp(a));
+
+        Ok(std::iter::from_fn(move || {
+            let (d, n) = match rest.first() {
+                Some(&(d, n)) if d > 0 => (d, n),
+                _ => return None,
+            };
+            let edges: Vec<(usize, usize)> = rest[1..=d]
+                .iter_mut()
+                .map(|other| {
+                    other.0 -= 1;
+                    (n, other.1)
+                })
+                .collect();
+            // Les sommets décrémentés et les suivants restent triés: une fusion en O(S) suffit.
+            let (low, high) = rest[1..].split_at(d);
+            let mut merged = Vec::with_capacity(rest.len() - 1);
+            let (mut i, mut j) = (0, 0);
+            while i < low.len() && j < high.len() {
+                if low[i] > high[j] {
+                    merged.push(low[i]);
+                    i += 1;
+                } else {
+                    merged.push(high[j]);
+                    j += 1;
+                }
+            }
+            merged.extend_from_slice(&low[i..]);
+            merged.extend_from_slice(&high[j..]);
+            rest = merged;
+            Some(edges)
+        })
+        .flatten())
+    }
+    /// Génère un graphe de Chung-Lu: les sommets `i` et `j` sont reliés avec la probabilité
+    /// `min(1, w[i]*w[j]/Σw)` et ont donc en espérance le degré `weights[i]`. Utilise l'algorithme
+    /// de Miller et Hagberg qui saute directement à la prochaine arête. Complexité: O(S log S + A).
+    pub fn gen_chung_lu<R: Rng + ?Sized>(weights: &[f64], r: &mut R) -> Graph {
+        Graph::new_iter(Graph::stream_chung_lu(weights, r), Some(weights.len()))
+    }
+    /// Arêtes du graphe de Chung-Lu, voir `gen_chung_lu`. Mémoire utilisée: O(S).
+    pub fn stream_chung_lu<'a, R: Rng + ?Sized>(
+        weights: &[f64],
+        r: &'a mut R,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let n = weights.len();
+        let total: f64 = weights.iter().sum();
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| weights[b].total_cmp(&weights[a]).then(a.cmp(&b)));
+        let w: Vec<f64> = order.iter().map(|&i| weights[i]).collect();
+
+        // Sommet courant u, prochain candidat v et probabilité p de l'arête précédente.
+        let (mut u, mut v, mut p) = (0, 1, 0.0);
+        if n > 1 && total > 0.0 {
+            p = (w[0] * w[1] / total).min(1.0);
+        }
+        std::iter::from_fn(move || loop {
+            if v >= n || p <= 0.0 {
+                u += 1;
+                if u + 1 >= n || total <= 0.0 {
+                    return None;
+                }
+                v = u + 1;
+                p = (w[u] * w[v] / total).min(1.0);
+                continue;
+            }
+            v = v.saturating_add(skip(r, p));
+            if v >= n {
+                continue;
+            }
+            let q = (w[u] * w[v] / total).min(1.0);
+            let accepted = r.gen::<f64>() < q / p;
+            p = q;
+            v += 1;
+            if accepted {
+                return Some((order[u], order[v - 1]));
+            }
+        })
+    }
+}
+
+/// Range la paire de sommets par ordre croissant.
+fn ordered(pair: &[usize]) -> (usize, usize) {
+    (pair[0].min(pair[1]), pair[0].max(pair[1]))
+}
+
+impl Stats {
+    /// Suite des degrés décrite par `degree_distrib`, triée par ordre croissant. Sert à générer
+    /// un modèle nul avec `Graph::gen_configuration` ou `Graph::gen_chung_lu`.
+    pub fn degree_sequence(&self) -> Vec<usize> {
+        self.degree_distrib
+            .iter()
+            .enumerate()
+            .flat_map(|(d, &nb)| std::iter::repeat_n(d, nb))
+            .collect()
+    }
+}
+#[test]
+fn test_is_graphical() {
+    assert!(is_graphical(&[]));
+    assert!(is_graphical(&[0, 0]));
+    assert!(is_graphical(&[1, 1, 2, 2, 4]));
+    assert!(is_graphical(&[4, 4, 4, 4, 4]));
+    assert!(!is_graphical(&[1, 1, 1]));
+    assert!(!is_graphical(&[4, 4, 4, 4, 4, 4, 1]));
+    assert!(!is_graphical(&[5, 1, 1, 1, 1]));
+}
+#[test]
+fn test_havel_hakimi() {
+    let degrees = [1, 1, 2, 2, 4, 3, 3];
+    let g = Graph::havel_hakimi(&degrees).unwrap();
+    let got: Vec<usize> = g.adjacency_list.iter().map(|c| c.len()).collect();
+    assert_eq!(degrees.to_vec(), got);
+    assert!(Graph::havel_hakimi(&[3, 3, 1, 1]).is_err());
+    assert!(Graph::stream_havel_hakimi(&[3, 3, 1, 1]).is_err());
+}
+#[test]
+fn gen_configuration() {
+    use super::rng;
+    let degrees = [3, 3, 2, 2, 2, 1, 1];
+    let multi = Graph::gen_configuration(&degrees, Configuration::Multi, &mut rng(0)).unwrap();
+    let got: Vec<usize> = multi.adjacency_list.iter().map(|c| c.len()).collect();
+    assert_eq!(degrees.to_vec(), got);
+
+    let g = Graph::gen_configuration(&degrees, Configuration::Rejection(1000), &mut rng(0));
+    let g = g.unwrap();
+    let got: Vec<usize> = g.adjacency_list.iter().map(|c| c.len()).collect();
+    assert_eq!(degrees.to_vec(), got);
+
+    let g = Graph::gen_configuration(&degrees, Configuration::Erased, &mut rng(0)).unwrap();
+    assert!(g.edges() <= 7);
+    assert!(Graph::gen_configuration(&[1, 2], Configuration::Multi, &mut rng(0)).is_err());
+
+    let s = g.stats();
+    assert_eq!(g.len(), s.degree_sequence().len());
+}
+#[test]
+fn gen_chung_lu() {
+    let weights = vec![10.0; 1000];
+    let g = Graph::gen_chung_lu(&weights, &mut super::rng(4));
+    let average = 2.0 * g.edges() as f64 / 1000.0;
+    assert!((average - 10.0).abs() < 0.5, "average degree: {}", average);
+}
@@ -0,0 +1,191 @@
+use super::gen::below;
+use super::{Graph, Preferential};
+use rand::Rng;
+
+impl Graph {
+    /// Construit l'arbre de `sequence.len()+2` sommets dont la suite de Prüfer est `sequence`.
+    /// Complexité: O(S).
+    /// ```
+    /// let g = graph::Graph::from_prufer(&[3, 3, 3, 4]).unwrap();
+    /// let edges: Vec<(usize, usize)> = g.edge_list().filter(|(a, b)| a < b).collect();
+    /// assert_eq!(vec![(0, 3), (1, 3), (2, 3), (3, 4), (4, 5)], edges);
+    /// ```
+    pub fn from_prufer(sequence: &[usize]) -> Result<Graph, String> {
+        let n = sequence.len() + 2;
+        let edges = Graph::stream_prufer(sequence.to_vec())?;
+        Ok(Graph::new_iter(edges, Some(n)))
+    }
+    /// Arêtes de l'arbre dont la suite de Prüfer est `sequence`, voir `from_prufer`.
+    pub fn stream_prufer(
+        sequence: Vec<usize>,
+    ) -> Result<impl Iterator<Item = (usize, usize)>, String> {
+        let n = sequence.len() + 2;
+        if let Some(x) = sequence.iter().find(|&&x| x >= n) {
+            return Err(format!("The node {} is out of the tree of {} nodes", x, n));
+        }
+
+        let mut degree = vec![1; n];
+        sequence.iter().for_each(|&x| degree[x] += 1);
+
+        // ptr avance sur les feuilles dans l'ordre croissant, leaf est la plus petite feuille.
+        let mut ptr = degree.iter().position(|&d| d == 1).unwrap_or(0);
+        let mut leaf = ptr;
+        let mut sequence = sequence.into_iter();
+        let mut done = false;
+        Ok(std::iter::from_fn(move || {
+            let x = match sequence.next() {
+                Some(x) => x,
+                None if done => return None,
+                None => {
+                    done = true;
+                    return Some((leaf, n - 1));
+                }
+            };
+            let edge = (leaf, x);
+            degree[x] -= 1;
+            if x < ptr && degree[x] == 1 {
+                leaf = x;
+            } else {
+                ptr += 1;
+                while degree[ptr] != 1 {
+                    ptr += 1;
+                }
+                leaf = ptr;
+            }
+            Some(edge)
+        }))
+    }
+    /// Génère un arbre étiqueté uniforme parmi les `size^(size-2)` arbres de `size` sommets, à
+    /// partir d'une suite de Prüfer aléatoire.
+    pub fn gen_prufer_tree<R: Rng + ?Sized>(size: usize, r: &mut R) -> Graph {
+        Graph::new_iter(Graph::stream_prufer_tree(size, r), Some(size))
+    }
+    /// Arêtes de l'arbre de Prüfer aléatoire, voir `gen_prufer_tree`. Mémoire utilisée: O(S).
+    pub fn stream_prufer_tree<R: Rng + ?Sized>(
+        size: usize,
+        r: &mut R,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let sequence: Vec<usize> = (2..size).map(|_| below(r, size)).collect();
+        Graph::stream_prufer(sequence)
+            .expect("the Prüfer sequence is valid")
+            .take(size.saturating_sub(1))
+    }
+    /// Génère un arbre récursif aléatoire: chaque nouveau sommet est relié à un sommet précédent
+    /// choisi uniformément.
+    pub fn gen_recursive_tree<R: Rng + ?Sized>(size: usize, r: &mut R) -> Graph {
+        Graph::new_iter(Graph::stream_recursive_tree(size, r), Some(size))
+    }
+    /// Arêtes de l'arbre récursif aléatoire, voir `gen_recursive_tree`. La mémoire utilisée est
+    /// constante.
+    pub fn stream_recursive_tree<'a, R: Rng + ?Sized>(
+        size: usize,
+        r: &'a mut R,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        (1..size).map(move |i| (below(r, i), i))
+    }
+    /// Génère un arbre préférentiel: chaque nouveau sommet est relié à un sommet précédent choisi
+    /// proportionnellement à son degré (modèle de Barabàsi-Albert avec `m = 1`).
+    pub fn gen_preferential_tree<R: Rng + ?Sized>(size: usize, r: &mut R) -> Graph {
+        Graph::new_iter(Graph::stream_preferential_tree(size, r), Some(size))
+    }
+    /// Arêtes de l'arbre préférentiel, voir `gen_preferential_tree` et `stream_preferential`.
+    pub fn stream_preferential_tree<'a, R: Rng + ?Sized>(
+        size: usize,
+        r: &'a mut R,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let param = Preferential {
+            m: 1,
+            ..Preferential::default()
+        };
+        Graph::stream_preferential(Some(&Graph::path(2)), size, &param, r)
+            .take(size.saturating_sub(1))
+    }
+    /// Tire un arbre couvrant uniforme de chaque composante connexe avec l'algorithme de Wilson:
+    /// depuis chaque sommet hors de l'arbre, une marche aléatoire est effectuée jusqu'à atteindre
+    /// l'arbre, puis le chemin sans ses boucles est ajouté à l'arbre. Retourne la forêt couvrante
+    /// avec les mêmes sommets que le graphe.
+    pub fn random_spanning_tree<R: Rng + ?Sized>(&self, r: &mut R) -> Graph {
+        Graph::new_iter(self.stream_random_spanning_tree(r), Some(self.len()))
+    }
+    /// Arêtes de la forêt couvrante aléatoire, voir `random_spanning_tree`. Les racines sont
+    /// choisies avant de retourner l'itérateur. Mémoire utilisée en plus du graphe: O(S).
+    pub fn stream_random_spanning_tree<'a, R: Rng + ?Sized>(
+        &'a self,
+        r: &'a mut R,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let mut in_tree = vec![false; self.len()];
+        let mut next = vec![0; self.len()];
+
+        // Une racine par composante connexe.
+        let whitelist = vec![true; self.len()];
+        let mut seen = vec![false; self.len()];
+        for root in 0..self.len() {
+            if !seen[root] {
+                in_tree[root] = true;
+                self.bfs(root, &whitelist, &mut |n, _| seen[n] = true);
+            }
+        }
+
+        (0..self.len()).flat_map(move |start| {
+            // Marche aléatoire, seul le dernier successeur de chaque sommet est retenu ce qui
+            // efface les boucles.
+            let mut u = start;
+            while !in_tree[u] {
+                let children = &self.adjacency_list[u];
+                next[u] = children[below(r, children.len())];
+                u = next[u];
+            }
+            let mut path = Vec::new();
+            let mut u = start;
+            while !in_tree[u] {
+                in_tree[u] = true;
+                path.push((u, next[u]));
+                u = next[u];
+            }
+            path
+        })
+    }
+}
+#[test]
+fn gen_trees() {
+    use super::rng;
+    let trees = [
+        Graph::gen_prufer_tree(300, &mut rng(0)),
+        Graph::gen_recursive_tree(300, &mut rng(0)),
+        Graph::gen_preferential_tree(300, &mut rng(0)),
+        Graph::grid(&[15, 20]).random_spanning_tree(&mut rng(0)),
+    ];
+    for g in trees.iter() {
+        assert_eq!(300, g.len());
+        assert_eq!(299, g.edges());
+        let dist = g.bfs(0, &[true; 300], &mut |_, _| {});
+        assert!(dist.iter().all(|d| d.is_some()));
+        // Tout l'arbre est élagué, le diamètre vient seulement des sous-arbres.
+        let (whitelist, _, longest) = g.mark_tree();
+        assert!(whitelist.iter().all(|w| !w));
+        assert_eq!(longest, g.distance());
+    }
+    assert!(Graph::from_prufer(&[5]).is_err());
+}
+#[test]
+fn spanning_tree_uniform() {
+    // Le cycle de 4 sommets a 4 arbres couvrants, chacun sans une des arêtes.
+    let g = Graph::cycle(4);
+    let mut r = super::rng(1);
+    let mut count = [0; 4];
+    for _ in 0..4000 {
+        let t = g.random_spanning_tree(&mut r);
+        let missing = (0..4).find(|&i| !t.adjacency_list[i].contains(&((i + 1) % 4)));
+        count[missing.unwrap()] += 1;
+    }
+    assert!(
+        count.iter().all(|&c| (900..1100).contains(&c)),
+        "{:?}",
+        count
+    );
+
+    let mut forest = Graph::path(3);
+    forest.push((3, 4));
+    forest.push((5, 5));
+    assert_eq!(3, forest.random_spanning_tree(&mut r).edges());
+}